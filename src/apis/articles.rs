@@ -0,0 +1,481 @@
+//! `Articles` resource: listing, feeds, CRUD, and favorites.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::context::{Claims, RateLimitState};
+use crate::models::{Article, ChangeEntry, GenericErrorModel, NewArticle, UpdateArticle};
+
+/// How `list_articles` should order its results. Implementors are
+/// responsible for the actual ordering; this only carries the caller's
+/// request through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArticleSort {
+    /// Newest first. The default when `sort` is omitted.
+    #[default]
+    Recency,
+    /// Most-favorited first.
+    Favorites,
+}
+
+impl ArticleSort {
+    /// Every valid value, in the order clients should try them, for
+    /// `GET /api/articles/options`.
+    pub const ALL: &'static [ArticleSort] = &[ArticleSort::Recency, ArticleSort::Favorites];
+
+    /// This variant's `?sort=` wire value, matching its
+    /// `#[serde(rename_all = "snake_case")]` representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArticleSort::Recency => "recency",
+            ArticleSort::Favorites => "favorites",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ListArticlesParams {
+    pub tag: Option<String>,
+    pub author: Option<String>,
+    /// Like `author`, but for filtering by any of several authors at
+    /// once: a comma-separated list of usernames, e.g.
+    /// `?authors=jake,jacob`. `author` and `authors` aren't mutually
+    /// exclusive at this layer — implementors decide how (or whether) to
+    /// combine them.
+    pub authors: Option<String>,
+    pub favorited: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    #[serde(default)]
+    pub sort: ArticleSort,
+    /// Restricts results to articles whose title starts with this prefix
+    /// (case-insensitive), e.g. `?title_prefix=a` for an A-Z browse index.
+    pub title_prefix: Option<String>,
+    /// Full-text search query, matched against whatever fields the
+    /// implementor indexes (typically title, description, and body).
+    /// This crate doesn't define the matching semantics — it's just
+    /// carried through to `list_articles` like every other filter.
+    pub q: Option<String>,
+}
+
+impl ListArticlesParams {
+    /// Splits `authors` on commas and trims whitespace, dropping empty
+    /// segments. Returns an empty vec if `authors` is unset.
+    pub fn authors_list(&self) -> Vec<&str> {
+        self.authors
+            .as_deref()
+            .map(|authors| {
+                authors
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|author| !author.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Response for `GET /api/articles`.
+#[derive(Debug, Clone)]
+pub enum ListArticlesResponse {
+    Status200_OK(Vec<Article>, i64),
+}
+
+/// Response for `GET /api/articles/feed`.
+#[derive(Debug, Clone)]
+pub enum FeedArticlesResponse {
+    Status200_OK(Vec<Article>, i64),
+    Status401_Unauthorized,
+}
+
+/// Response for `GET /api/user/drafts`.
+#[derive(Debug, Clone)]
+pub enum DraftArticlesResponse {
+    Status200_OK(Vec<Article>, i64),
+    Status401_Unauthorized,
+}
+
+/// One entry in the response to `GET /api/articles/leaderboard`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct LeaderboardEntry {
+    pub slug: String,
+    pub title: String,
+    pub favorites_count: i64,
+}
+
+/// Response for `GET /api/articles/leaderboard`.
+#[derive(Debug, Clone)]
+pub enum LeaderboardResponse {
+    /// Entries ordered most-favorited first. Implementors decide how many
+    /// to return; the router doesn't truncate.
+    Status200_OK(Vec<LeaderboardEntry>),
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LeaderboardParams {
+    pub limit: Option<i64>,
+}
+
+/// Response for `GET /api/articles/:slug/export`. Carries the raw article
+/// body so the router can serve `Range` requests against it; the router
+/// itself handles byte-range slicing.
+#[derive(Debug, Clone)]
+pub enum ExportArticleResponse {
+    Status200_OK(String),
+    Status404_NotFound,
+    /// The export is being prepared in the background; `status_url`
+    /// points to where the caller can poll for it. Implementors that
+    /// always export synchronously simply never return this variant.
+    Status202_Accepted { status_url: String },
+}
+
+/// Response for `GET /api/articles/:slug`.
+#[derive(Debug, Clone)]
+pub enum GetArticleResponse {
+    Status200_OK(Article),
+    Status404_NotFound,
+    /// The slug existed but its article has since been deleted. Distinct
+    /// from `404` so clients holding a tombstoned slug can purge caches
+    /// rather than treat it as "never existed". Implementors without
+    /// tombstone tracking simply never return this variant.
+    Status410_Gone,
+}
+
+/// Response for `POST /api/articles`.
+#[derive(Debug, Clone)]
+pub enum CreateArticleResponse {
+    Status201_Created(Article),
+    Status401_Unauthorized,
+    Status422_UnprocessableEntity(GenericErrorModel),
+}
+
+/// Response for `PUT /api/articles/:slug`.
+#[derive(Debug, Clone)]
+pub enum UpdateArticleResponse {
+    Status200_OK(Article),
+    Status401_Unauthorized,
+    Status403_Forbidden,
+    Status404_NotFound,
+    Status422_UnprocessableEntity(GenericErrorModel),
+}
+
+/// Response for `DELETE /api/articles/:slug`.
+#[derive(Debug, Clone)]
+pub enum DeleteArticleResponse {
+    Status200_OK,
+    Status401_Unauthorized,
+    Status403_Forbidden,
+    Status404_NotFound,
+    /// The deletion is queued for background processing rather than
+    /// done by the time this returns; `status_url` points to where the
+    /// caller can poll for completion. Implementors that always delete
+    /// synchronously simply never return this variant.
+    Status202_Accepted { status_url: String },
+}
+
+/// The outcome of one item in a `POST /api/articles/bulk` request, in the
+/// same order as the submitted `articles`.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkImportResult {
+    Created(Article),
+    Rejected(GenericErrorModel),
+}
+
+/// Response for `POST /api/articles/bulk`.
+#[derive(Debug, Clone)]
+pub enum BulkImportArticlesResponse {
+    /// One result per submitted article, in order, mixing successes and
+    /// failures — hence `207 Multi-Status` rather than `200`/`422`.
+    Status207_MultiStatus(Vec<BulkImportResult>),
+    Status401_Unauthorized,
+}
+
+/// Response for `POST /api/articles/:slug/favorite`.
+#[derive(Debug, Clone)]
+pub enum FavoriteArticleResponse {
+    Status200_OK(Article),
+    Status401_Unauthorized,
+    Status404_NotFound,
+}
+
+/// Response for `DELETE /api/articles/:slug/favorite`.
+#[derive(Debug, Clone)]
+pub enum UnfavoriteArticleResponse {
+    Status200_OK(Article),
+    Status401_Unauthorized,
+    Status404_NotFound,
+}
+
+/// Body for `GET /api/articles/options`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ListArticlesOptions {
+    /// Every valid `?sort=` value for `GET /api/articles`.
+    pub sort: Vec<&'static str>,
+}
+
+/// Response for `GET /api/articles/changes`.
+#[derive(Debug, Clone)]
+pub enum ChangesSinceResponse {
+    /// Entries with id greater than the requested `since`, in id order,
+    /// plus the `next_since` to request to continue the feed. `None`
+    /// for `next_since` means the caller has caught up.
+    Status200_OK(Vec<ChangeEntry>, Option<i64>),
+}
+
+/// Checks a (already-trimmed) article title for the bound every
+/// implementor's `create_article_validation`/`update_article_validation`
+/// enforces by default: non-blank, no control characters (which would
+/// otherwise produce an ugly slug or garbled display), and no longer
+/// than 255 characters.
+pub(crate) fn title_validation(title: &str) -> Vec<String> {
+    let mut errors = Vec::new();
+    if title.is_empty() {
+        errors.push("title can't be blank".to_string());
+    }
+    if title.chars().any(|c| c.is_control()) {
+        errors.push("title can't contain control characters".to_string());
+    }
+    if title.chars().count() > 255 {
+        errors.push("title is too long (maximum is 255 characters)".to_string());
+    }
+    errors
+}
+
+/// The slug-generation algorithm behind `GET /api/articles/slugify`:
+/// Unicode-normalizes `title` to NFKD, drops combining diacritics (so
+/// `"Über"` folds to `"uber"` rather than keeping the umlaut), lowercases
+/// ASCII letters and digits, and collapses every other run of characters
+/// to a single `-`, trimming a leading or trailing one.
+///
+/// This is only a preview/default — implementors are free to compute
+/// their own slug in `create_article` (e.g. using the request's locale
+/// for true transliteration) rather than call this.
+pub(crate) fn slugify(title: &str) -> String {
+    use unicode_normalization::UnicodeNormalization;
+    let mut slug = String::with_capacity(title.len());
+    let mut last_was_dash = true;
+    for c in title.nfkd() {
+        if ('\u{0300}'..='\u{036f}').contains(&c) {
+            continue;
+        }
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+            last_was_dash = false;
+        } else if !last_was_dash {
+            slug.push('-');
+            last_was_dash = true;
+        }
+    }
+    if slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
+#[async_trait]
+pub trait Articles {
+    async fn list_articles(&self, params: ListArticlesParams) -> ListArticlesResponse;
+
+    async fn feed_articles(&self, claims: Claims, params: ListArticlesParams) -> FeedArticlesResponse;
+
+    /// The caller's own unpublished articles (`published: Some(false)` at
+    /// creation, or flipped via `update_article`), for `GET
+    /// /api/user/drafts`. Public listings (`list_articles`,
+    /// `feed_articles`) exclude drafts; this is the only route that
+    /// surfaces them, and only to their author.
+    async fn draft_articles(&self, claims: Claims, params: ListArticlesParams) -> DraftArticlesResponse;
+
+    /// `claims` is resolved once per request by the router (via
+    /// [`crate::apis::auth::ClaimsResolver`]) and passed in here so
+    /// implementors can compute the caller's `favorited` flag without a
+    /// second claims lookup of their own.
+    async fn get_article(&self, claims: Option<Claims>, slug: String) -> GetArticleResponse;
+
+    /// Resolves [`Article::can_edit`] for the single-article read path.
+    /// Defaults to "the caller is the article's author", matching the
+    /// authorization most implementations of `update_article`/
+    /// `delete_article` already enforce. `claims` is `None` for an
+    /// anonymous caller, which can never edit anything.
+    fn can_edit(&self, claims: Option<&Claims>, article: &Article) -> bool {
+        claims.is_some_and(|claims| claims.username == article.author.username)
+    }
+
+    /// Returns the raw body of the article at `slug`, for `GET
+    /// /api/articles/:slug/export`. The router serves `Range` requests
+    /// against the returned bytes, so implementors don't need to handle
+    /// partial content themselves.
+    async fn export_article(&self, slug: String) -> ExportArticleResponse;
+
+    /// `locale` is the caller's preferred locale, taken from the request's
+    /// `Accept-Language` header (its first, highest-priority tag, without
+    /// attempting full RFC 4647 negotiation), for implementors that
+    /// transliterate titles into slugs in a locale-aware way (e.g.
+    /// German `"Über"` slugifying to `"ueber"` instead of `"uber"`).
+    /// `None` if the header was absent or unparseable.
+    async fn create_article(
+        &self,
+        claims: Claims,
+        new_article: NewArticle,
+        locale: Option<String>,
+    ) -> CreateArticleResponse;
+
+    /// Validates a new article's title before it reaches
+    /// `create_article`, the way `Users::create_user_validation` guards
+    /// `register`. Returns the messages to surface in a `422` response
+    /// body, or an empty vec if the title is acceptable. The router
+    /// trims `new_article.title` before this runs, so leading/trailing
+    /// whitespace never counts against the length bound or reaches the
+    /// slug generator.
+    fn create_article_validation(&self, new_article: &NewArticle) -> Vec<String> {
+        title_validation(&new_article.title)
+    }
+
+    /// Validates an update payload's title the way
+    /// `create_article_validation` guards `create_article`. Only checked
+    /// when `update.title` is set.
+    fn update_article_validation(&self, update: &UpdateArticle) -> Vec<String> {
+        match &update.title {
+            Some(title) => title_validation(title),
+            None => Vec::new(),
+        }
+    }
+
+    /// Creates several articles in one request, for `POST
+    /// /api/articles/bulk`. Each item in `new_articles` succeeds or fails
+    /// independently — a bad title on item 3 doesn't stop items 1, 2, and
+    /// 4 from being created.
+    async fn bulk_import_articles(
+        &self,
+        claims: Claims,
+        new_articles: Vec<NewArticle>,
+    ) -> BulkImportArticlesResponse;
+
+    async fn update_article(
+        &self,
+        claims: Claims,
+        slug: String,
+        update: UpdateArticle,
+    ) -> UpdateArticleResponse;
+
+    async fn delete_article(&self, claims: Claims, slug: String) -> DeleteArticleResponse;
+
+    async fn favorite_article(&self, claims: Claims, slug: String) -> FavoriteArticleResponse;
+
+    async fn unfavorite_article(&self, claims: Claims, slug: String) -> UnfavoriteArticleResponse;
+
+    /// The most-favorited articles, for `GET /api/articles/leaderboard`.
+    /// `limit` is the caller's requested count (defaulted by the router to
+    /// `20` when omitted); implementors may cap it lower.
+    async fn leaderboard(&self, limit: i64) -> LeaderboardResponse;
+
+    /// Change-feed entries with id greater than `since`, in id order, up
+    /// to `limit` entries, for `GET /api/articles/changes`. Sync clients
+    /// poll this instead of re-fetching `list_articles` from scratch:
+    /// pass the last response's `next_since` back in as `since` to pick
+    /// up where they left off. Entries include tombstones
+    /// ([`ChangeEntry::Deleted`]) for articles deleted since `since`, not
+    /// just creates and updates. The router validates `since >= 0`
+    /// before calling this; `limit` is defaulted by the router to `20`
+    /// when omitted.
+    async fn changes_since(&self, since: i64, limit: i64) -> ChangesSinceResponse;
+
+    /// The caller's current rate-limit window, if this implementor tracks
+    /// one. When present, the router adds `X-RateLimit-Remaining` and
+    /// `X-RateLimit-Reset` headers to an otherwise-successful
+    /// `create_article` response. Defaults to `None`.
+    fn rate_limit_state(&self) -> Option<RateLimitState> {
+        None
+    }
+
+    /// Whether `slug` is an acceptable path segment for this deployment,
+    /// checked by the router before `GET`/`PUT`/`DELETE
+    /// /api/articles/:slug` and `GET /api/articles/:slug/oembed` reach
+    /// their trait method. A 404 is returned without calling the trait
+    /// method at all when this returns `false`. The favorite, comment, and
+    /// export routes under `/api/articles/:slug` don't consult it and pass
+    /// every slug through to their trait method. The default accepts everything;
+    /// override to reject, e.g., reserved words like `feed` or `export`
+    /// that would otherwise collide with other routes under a case- or
+    /// percent-encoding mismatch.
+    fn slug_is_allowed(&self, slug: &str) -> bool {
+        let _ = slug;
+        true
+    }
+
+    /// Renders `markdown` (an article body, as stored) to HTML, for `GET
+    /// /api/articles/:slug?format=html`. Returning `None` (the default)
+    /// means this implementor doesn't support HTML rendering, and the
+    /// router ignores `?format=html` entirely, serving the raw markdown
+    /// body as usual.
+    ///
+    /// The returned HTML is served as-is: this crate doesn't parse or
+    /// touch it, so sanitizing against XSS (stripping `<script>`, inline
+    /// event handlers, `javascript:` URLs, etc.) is this method's
+    /// responsibility, not something to bolt on afterward.
+    fn render_body_html(&self, markdown: &str) -> Option<String> {
+        let _ = markdown;
+        None
+    }
+
+    /// A human-readable note that the upcoming `get_article`/
+    /// `list_articles` response is degraded in some way (e.g. served from
+    /// a stale cache during an outage), surfaced as an HTTP `Warning`
+    /// header per RFC 7234 §5.5 rather than failing the request outright.
+    /// Defaults to `None`.
+    fn warning(&self) -> Option<String> {
+        None
+    }
+
+    /// Whether the upcoming `list_articles` response couldn't fully
+    /// populate per-caller personalization (`favorited`, and each
+    /// article's author's `following`) — e.g. an implementor that skips a
+    /// slow per-user join under load and serves
+    /// [`crate::models::PersonalizationFlag::NotApplicable`] instead.
+    /// When `true`, the router adds an `X-Partial-Personalization: true`
+    /// header, so a client that needs accurate personalization knows to
+    /// re-fetch the affected articles individually rather than trust the
+    /// list. Defaults to `false`.
+    fn partial_personalization(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugifies_plain_ascii() {
+        assert_eq!(slugify("Do You Have Aviator Goggles?"), "do-you-have-aviator-goggles");
+    }
+
+    #[test]
+    fn slugifies_accented_titles() {
+        assert_eq!(slugify("Café Über Ñoño"), "cafe-uber-nono");
+    }
+
+    #[test]
+    fn slugifies_symbols_to_hyphens() {
+        assert_eq!(slugify("C++ & Rust!"), "c-rust");
+    }
+
+    #[test]
+    fn slugify_trims_leading_and_trailing_punctuation() {
+        assert_eq!(slugify("  -- dragons --  "), "dragons");
+    }
+
+    #[test]
+    fn article_sort_as_str_matches_its_serde_rename() {
+        assert_eq!(ArticleSort::Recency.as_str(), "recency");
+        assert_eq!(ArticleSort::Favorites.as_str(), "favorites");
+    }
+
+    #[test]
+    fn article_sort_all_covers_every_variant() {
+        assert_eq!(ArticleSort::ALL.len(), 2);
+        assert!(ArticleSort::ALL.contains(&ArticleSort::Recency));
+        assert!(ArticleSort::ALL.contains(&ArticleSort::Favorites));
+    }
+}
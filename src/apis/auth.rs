@@ -0,0 +1,27 @@
+//! Claims resolution: turning the raw `Authorization` header into
+//! [`Claims`], in a way deployments can customize (JWT verification,
+//! opaque session lookup, etc).
+
+use async_trait::async_trait;
+
+use crate::context::Claims;
+
+#[async_trait]
+pub trait ClaimsResolver {
+    /// Resolves `authorization` (the raw `Authorization` header value, if
+    /// one was sent) into [`Claims`]. Returns `None` for a missing,
+    /// malformed, or invalid/expired token.
+    ///
+    /// The default implementation expects `Token <jwt>` and treats the
+    /// token verbatim as the user id, without verifying it — it exists so
+    /// the router has something to call before an implementor wires up
+    /// real verification, not as a production-ready default.
+    async fn resolve_claims(&self, authorization: Option<&str>) -> Option<Claims> {
+        let header = authorization?;
+        let token = header.strip_prefix("Token ")?;
+        Some(Claims {
+            user_id: token.to_string(),
+            username: String::new(),
+        })
+    }
+}
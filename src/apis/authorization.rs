@@ -0,0 +1,65 @@
+//! Authorization: a single chokepoint mutating handlers call before
+//! invoking the operation itself, kept separate from authentication
+//! ([`crate::apis::auth::ClaimsResolver`]) so "who is this" and "can they
+//! do that" can evolve independently.
+
+use async_trait::async_trait;
+
+use crate::context::Claims;
+
+/// Identifies which mutation a handler is about to perform, for
+/// [`Authorization::authorize`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    CreateArticle,
+    UpdateArticle,
+    DeleteArticle,
+    FavoriteArticle,
+    UnfavoriteArticle,
+    AddComment,
+    DeleteComment,
+    FollowProfile,
+    UnfollowProfile,
+    UpdateUser,
+    DeleteAccount,
+    RotateToken,
+    RenameTag,
+    DeleteTag,
+    /// A `POST /api/articles/bulk` import of one or more articles at once.
+    /// Unlike [`Operation::CreateArticle`], a single authorization check
+    /// covers the whole batch rather than one per article — implementors
+    /// that want per-article checks should inspect `claims` themselves
+    /// inside `bulk_import_articles`.
+    BulkImportArticles,
+    /// A `DELETE /api/articles/:slug/comments` moderation delete of one or
+    /// more comments on the article identified by
+    /// `ResourceId::Existing(slug)`, as opposed to [`Operation::DeleteComment`],
+    /// which acts on a single comment by id.
+    DeleteComments,
+}
+
+/// The resource an [`Operation`] acts on, identified the same way its own
+/// route does (an article's slug, a comment's id as a string, a profile's
+/// username, a tag's name).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceId {
+    /// An existing resource.
+    Existing(String),
+    /// No existing resource — e.g. [`Operation::CreateArticle`], which
+    /// creates one rather than acting on one already on file.
+    New,
+}
+
+#[async_trait]
+pub trait Authorization {
+    /// Returns whether `claims` may perform `operation` on `resource`,
+    /// checked by the router before the mutating handler's trait method
+    /// runs; a `false` becomes `403 Forbidden` without the operation ever
+    /// being attempted. Defaults to `true` for everything, preserving
+    /// today's behavior (authentication alone gates mutations) until an
+    /// implementor opts into a real policy.
+    async fn authorize(&self, claims: &Claims, operation: Operation, resource: ResourceId) -> bool {
+        let _ = (claims, operation, resource);
+        true
+    }
+}
@@ -0,0 +1,119 @@
+//! `Comments` resource, nested under an article's slug.
+
+use async_trait::async_trait;
+
+use crate::context::Claims;
+use crate::models::{BatchResult, Comment, GenericErrorModel, NewComment, RecentComment};
+
+/// Response for `POST /api/articles/:slug/comments`.
+#[derive(Debug, Clone)]
+pub enum AddCommentResponse {
+    Status200_OK(Comment),
+    Status401_Unauthorized,
+    Status404_NotFound,
+    Status409_Conflict(GenericErrorModel),
+    Status422_UnprocessableEntity(GenericErrorModel),
+}
+
+/// Response for `GET /api/articles/:slug/comments`.
+#[derive(Debug, Clone)]
+pub enum GetCommentsResponse {
+    Status200_OK(Vec<Comment>),
+    Status404_NotFound,
+}
+
+/// Response for `DELETE /api/articles/:slug/comments/:id`.
+#[derive(Debug, Clone)]
+pub enum DeleteCommentResponse {
+    Status200_OK,
+    Status401_Unauthorized,
+    Status403_Forbidden,
+    Status404_NotFound,
+}
+
+/// Response for `DELETE /api/articles/:slug/comments?ids=1,2,3`.
+#[derive(Debug, Clone)]
+pub enum DeleteCommentsResponse {
+    /// One [`BatchResult`] per requested id, in the same order, mixing
+    /// successes and failures — e.g. an id that's already deleted, or
+    /// one the caller isn't authorized to remove, doesn't stop the rest
+    /// from succeeding.
+    Status200_OK(Vec<BatchResult<()>>),
+    Status401_Unauthorized,
+    Status404_NotFound,
+}
+
+/// Response for `GET /api/comments/recent`.
+#[derive(Debug, Clone)]
+pub enum GetRecentCommentsResponse {
+    Status200_OK(Vec<RecentComment>),
+}
+
+#[async_trait]
+pub trait Comments {
+    /// `max_comments_per_article` is
+    /// [`crate::context::ServerConfig::max_comments_per_article`], passed
+    /// through so this method can return
+    /// [`AddCommentResponse::Status409_Conflict`] once an article already
+    /// holds that many comments — this crate doesn't count an article's
+    /// existing comments itself, so the cap is only ever consulted here.
+    async fn add_comment(
+        &self,
+        claims: Claims,
+        slug: String,
+        comment: NewComment,
+        max_comments_per_article: Option<usize>,
+    ) -> AddCommentResponse;
+
+    async fn get_comments(&self, slug: String) -> GetCommentsResponse;
+
+    async fn delete_comment(&self, claims: Claims, slug: String, comment_id: i64) -> DeleteCommentResponse;
+
+    /// Deletes several comments on one article in a single request, for
+    /// moderation cleanup. Mirrors
+    /// [`crate::apis::articles::Articles::bulk_import_articles`]: each id
+    /// succeeds or fails independently, so one already-deleted or
+    /// not-owned id doesn't block the rest. Authorization — who may
+    /// delete whose comment — is entirely this method's responsibility,
+    /// same as `delete_comment`.
+    async fn delete_comments(
+        &self,
+        claims: Claims,
+        slug: String,
+        comment_ids: Vec<i64>,
+    ) -> DeleteCommentsResponse;
+
+    /// The most recent comments across every article, for `GET
+    /// /api/comments/recent` — a site-wide activity feed rather than one
+    /// scoped to a single article like [`Comments::get_comments`].
+    /// `claims` is resolved from the `Authorization` header if present but
+    /// authentication isn't required, the same as
+    /// [`crate::apis::users::Users::get_user_by_username`]; implementors
+    /// that personalize each comment author's `following` flag for the
+    /// caller can use it, and everyone else can ignore it. `limit` and
+    /// `offset` are defaulted by the router to `20` and `0` when omitted,
+    /// and a negative value of either is rejected with `400` before this
+    /// is called.
+    ///
+    /// This crate has no notion of a "public" vs. "unlisted" article, so
+    /// it can't filter comments by the visibility of their article itself
+    /// — implementors that distinguish the two should exclude
+    /// non-public articles' comments here before they ever reach the
+    /// router.
+    async fn get_recent_comments(
+        &self,
+        claims: Option<Claims>,
+        limit: i64,
+        offset: i64,
+    ) -> GetRecentCommentsResponse;
+
+    /// Whether the upcoming `get_comments` response couldn't fully
+    /// populate each comment author's `following` flag, the same signal
+    /// as [`crate::apis::articles::Articles::partial_personalization`]
+    /// but for `GET /api/articles/:slug/comments`. When `true`, the
+    /// router adds an `X-Partial-Personalization: true` header. Defaults
+    /// to `false`.
+    fn partial_personalization(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,23 @@
+//! One trait per Conduit resource. Implementors provide a type that
+//! implements all of these (typically via `impl` blocks split across
+//! modules) and hand it to [`crate::router::build_router`].
+
+pub mod articles;
+pub mod auth;
+pub mod authorization;
+pub mod comments;
+pub mod profiles;
+pub mod stats;
+pub mod tags;
+pub mod users;
+pub mod webhooks;
+
+pub use articles::Articles;
+pub use auth::ClaimsResolver;
+pub use authorization::Authorization;
+pub use comments::Comments;
+pub use profiles::Profiles;
+pub use stats::Stats;
+pub use tags::Tags;
+pub use users::Users;
+pub use webhooks::WebhookDispatcher;
@@ -0,0 +1,71 @@
+//! `Profiles` resource: public profiles and the follow relationship.
+
+use async_trait::async_trait;
+
+use crate::context::Claims;
+use crate::models::{Article, Profile};
+
+/// Response for `GET /api/profiles/:username`.
+#[derive(Debug, Clone)]
+pub enum GetProfileResponse {
+    Status200_OK(Profile),
+    Status404_NotFound,
+}
+
+/// Response for `GET /api/profiles/:username/summary`.
+#[derive(Debug, Clone)]
+pub enum ProfileSummaryResponse {
+    /// The profile plus their most recent articles, newest first. How many
+    /// articles is up to the implementor; the router doesn't truncate.
+    Status200_OK(Profile, Vec<Article>),
+    Status404_NotFound,
+}
+
+/// Response for `POST /api/profiles/:username/follow`.
+#[derive(Debug, Clone)]
+pub enum FollowResponse {
+    Status200_OK(Profile),
+    Status401_Unauthorized,
+    Status404_NotFound,
+}
+
+/// Response for `DELETE /api/profiles/:username/follow`.
+#[derive(Debug, Clone)]
+pub enum UnfollowResponse {
+    Status200_OK(Profile),
+    Status401_Unauthorized,
+    Status404_NotFound,
+}
+
+/// Response for `PUT /api/profiles/:username/follow`.
+#[derive(Debug, Clone)]
+pub enum SetFollowResponse {
+    Status200_OK(Profile),
+    Status401_Unauthorized,
+    Status404_NotFound,
+}
+
+#[async_trait]
+pub trait Profiles {
+    async fn get_profile(&self, claims: Option<Claims>, username: String) -> GetProfileResponse;
+
+    async fn follow_user(&self, claims: Claims, username: String) -> FollowResponse;
+
+    async fn unfollow_user(&self, claims: Claims, username: String) -> UnfollowResponse;
+
+    /// Sets the follow relationship to exactly `following`, for `PUT
+    /// /api/profiles/:username/follow` — an idempotent alternative to
+    /// `follow_user`/`unfollow_user` for clients that want to declare
+    /// the desired end state (e.g. a toggle UI that resubmits the same
+    /// request on retry) rather than issue a directional verb. Calling
+    /// it with the relationship already in the requested state is a
+    /// no-op that still returns the current profile, same as repeating a
+    /// `follow_user`/`unfollow_user` call.
+    async fn set_follow(&self, claims: Claims, username: String, following: bool) -> SetFollowResponse;
+
+    /// `username`'s public profile plus their latest articles in one
+    /// call, for `GET /api/profiles/:username/summary` — saves a client
+    /// two round trips (`get_profile` then `list_articles?author=...`)
+    /// when it only wants a profile page's worth of context.
+    async fn profile_summary(&self, claims: Option<Claims>, username: String) -> ProfileSummaryResponse;
+}
@@ -0,0 +1,20 @@
+//! `Stats` resource: aggregate counts across the whole deployment, for
+//! dashboards.
+
+use async_trait::async_trait;
+
+use crate::models::Stats as StatsSnapshot;
+
+/// Response for `GET /api/stats`.
+#[derive(Debug, Clone)]
+pub enum GetStatsResponse {
+    Status200_OK(StatsSnapshot),
+    /// [`crate::context::ServerConfig::public_stats`] is off and the
+    /// caller isn't authenticated.
+    Status401_Unauthorized,
+}
+
+#[async_trait]
+pub trait Stats {
+    async fn get_stats(&self) -> GetStatsResponse;
+}
@@ -0,0 +1,110 @@
+//! `Tags` resource: the flat list of tags used across all articles, plus
+//! admin-only management of that list.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::context::Claims;
+
+/// Response for `GET /api/tags`.
+#[derive(Debug, Clone)]
+pub enum GetTagsResponse {
+    Status200_OK(Vec<String>),
+}
+
+/// One entry in the response to `GET /api/tags/popular`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: i64,
+}
+
+/// Response for `GET /api/tags/popular`.
+#[derive(Debug, Clone)]
+pub enum PopularTagsResponse {
+    /// Ordered most-used first. Implementors decide how many to return.
+    Status200_OK(Vec<TagCount>),
+}
+
+/// Response for `GET /api/profiles/:username/tags`.
+#[derive(Debug, Clone)]
+pub enum AuthorTagsResponse {
+    Status200_OK(Vec<String>),
+    Status404_NotFound,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PopularTagsParams {
+    /// How many days back to count tag usage over. `None` means "all
+    /// time" — implementors aren't required to support windowing and can
+    /// treat every value the same way.
+    pub window_days: Option<i64>,
+}
+
+/// Response for `PUT /api/tags/:tag`.
+#[derive(Debug, Clone)]
+pub enum RenameTagResponse {
+    Status200_OK,
+    Status401_Unauthorized,
+    Status403_Forbidden,
+    Status404_NotFound,
+    Status422_UnprocessableEntity(crate::models::GenericErrorModel),
+}
+
+/// Response for `DELETE /api/tags/:tag`.
+#[derive(Debug, Clone)]
+pub enum DeleteTagResponse {
+    Status200_OK,
+    Status401_Unauthorized,
+    Status403_Forbidden,
+    Status404_NotFound,
+}
+
+#[async_trait]
+pub trait Tags {
+    async fn get_tags(&self) -> GetTagsResponse;
+
+    /// The most-used tags over the last `window_days` days (`None` for
+    /// all time), for `GET /api/tags/popular`.
+    async fn popular_tags(&self, window_days: Option<i64>) -> PopularTagsResponse;
+
+    /// The distinct tags used across `username`'s own articles, for
+    /// `GET /api/profiles/:username/tags`.
+    async fn author_tags(&self, username: String) -> AuthorTagsResponse;
+
+    /// Renames `tag` to `new_name` everywhere it's used. `claims` is
+    /// passed through unchecked; implementors are responsible for
+    /// rejecting non-admin callers with `Status403_Forbidden`.
+    async fn rename_tag(&self, claims: Claims, tag: String, new_name: String) -> RenameTagResponse;
+
+    /// Deletes `tag` everywhere it's used. `claims` is passed through
+    /// unchecked; implementors are responsible for rejecting non-admin
+    /// callers with `Status403_Forbidden`.
+    async fn delete_tag(&self, claims: Claims, tag: String) -> DeleteTagResponse;
+
+    /// Validates a tag's format (used for both the admin rename target and
+    /// new article tags). Returns the messages to surface in a `422`
+    /// response body, or an empty vec if the tag is acceptable.
+    fn tag_validation(&self, tag: &str) -> Vec<String> {
+        let mut errors = Vec::new();
+        if tag.trim().is_empty() {
+            errors.push("tag can't be blank".to_string());
+        }
+        if tag.chars().count() > 64 {
+            errors.push("tag is too long (maximum is 64 characters)".to_string());
+        }
+        errors
+    }
+
+    /// Non-fatal concerns about `tag` that don't justify rejecting it
+    /// (e.g. a deprecated naming convention, or mixed case that'll read
+    /// oddly next to existing all-lowercase tags). Unlike
+    /// [`Tags::tag_validation`], these never turn into a `422` — when
+    /// [`crate::context::ServerConfig::validation_warnings`] is on, the
+    /// router surfaces them in the response's `Warning` header instead
+    /// and still proceeds. The default produces no warnings.
+    fn tag_warnings(&self, tag: &str) -> Vec<String> {
+        let _ = tag;
+        Vec::new()
+    }
+}
@@ -0,0 +1,276 @@
+//! `Users` resource: registration, login, and the current-user endpoints.
+
+use async_trait::async_trait;
+
+use crate::context::{Claims, RateLimitState};
+use crate::models::{GenericErrorModel, NewUser, UpdateUser, User, UserDataExport};
+
+/// Response for `POST /api/users/login`.
+#[derive(Debug, Clone)]
+pub enum LoginResponse {
+    Status200_OK(User),
+    Status401_Unauthorized,
+    Status422_UnprocessableEntity(GenericErrorModel),
+    /// Too many failed attempts for this account/caller. Implementors
+    /// that track attempt counts return this instead of
+    /// `Status401_Unauthorized` once their configured threshold is hit;
+    /// `retry_after_secs` becomes the `Retry-After` header.
+    Status423_Locked { retry_after_secs: Option<u64> },
+}
+
+/// Response for `POST /api/users`.
+#[derive(Debug, Clone)]
+pub enum RegisterResponse {
+    Status201_Created(User),
+    /// `new_user.username` or `new_user.email` is already taken by another
+    /// account. Distinct from `Status422_UnprocessableEntity`, which is for
+    /// payloads that are malformed rather than merely unavailable — this
+    /// crate doesn't check uniqueness itself (see [`normalize_username`]),
+    /// so implementors that do return this instead once their own check
+    /// fails.
+    Status409_Conflict(GenericErrorModel),
+    Status422_UnprocessableEntity(GenericErrorModel),
+}
+
+/// Response for `GET /api/user`.
+#[derive(Debug, Clone)]
+pub enum CurrentUserResponse {
+    Status200_OK(User),
+    Status401_Unauthorized,
+}
+
+/// Response for `PUT /api/user`.
+#[derive(Debug, Clone)]
+pub enum UpdateUserResponse {
+    Status200_OK(User),
+    Status401_Unauthorized,
+    Status422_UnprocessableEntity(GenericErrorModel),
+    /// The caller's `If-Match` header didn't match the current user's
+    /// ETag, meaning someone else updated the profile since the caller
+    /// last read it.
+    Status412_PreconditionFailed,
+}
+
+/// Response for `POST /api/user/token`.
+#[derive(Debug, Clone)]
+pub enum RotateTokenResponse {
+    Status200_OK(User),
+    Status401_Unauthorized,
+}
+
+/// Response for `GET /api/users/:username`.
+#[derive(Debug, Clone)]
+pub enum GetUserByUsernameResponse {
+    Status200_OK(crate::models::PublicUser),
+    Status404_NotFound,
+}
+
+/// Response for `GET /api/user/export`.
+#[derive(Debug, Clone)]
+pub enum ExportUserDataResponse {
+    Status200_OK(UserDataExport),
+    Status401_Unauthorized,
+}
+
+/// Response for `DELETE /api/user`.
+#[derive(Debug, Clone)]
+pub enum DeleteAccountResponse {
+    Status204_NoContent,
+    Status401_Unauthorized,
+    /// The caller supplied a `password` that didn't match the account's
+    /// current one.
+    Status422_UnprocessableEntity(GenericErrorModel),
+}
+
+#[async_trait]
+pub trait Users {
+    async fn login(&self, credentials: crate::models::LoginUser) -> LoginResponse;
+
+    async fn register(&self, new_user: NewUser) -> RegisterResponse;
+
+    async fn current_user(&self, claims: Claims) -> CurrentUserResponse;
+
+    async fn update_user(&self, claims: Claims, update: UpdateUser) -> UpdateUserResponse;
+
+    /// Invalidates the caller's current token and issues a new one, for
+    /// `POST /api/user/token`. Useful after a suspected leak, without
+    /// requiring the user to re-enter their password.
+    async fn rotate_token(&self, claims: Claims) -> RotateTokenResponse;
+
+    /// The public subset of `username`'s account — no `email`, no
+    /// `token` — for `GET /api/users/:username`. `claims` is resolved
+    /// from the `Authorization` header if present but authentication
+    /// isn't required; implementors that personalize this response for
+    /// authenticated callers (e.g. admins seeing more) can use it, and
+    /// everyone else can ignore it.
+    async fn get_user_by_username(
+        &self,
+        claims: Option<Claims>,
+        username: String,
+    ) -> GetUserByUsernameResponse;
+
+    /// Every article, comment, and favorite on file for `claims`'s
+    /// account, for `GET /api/user/export`, a data-portability request
+    /// under regulations like the GDPR. The router serves this with
+    /// `Content-Disposition: attachment` so browsers download it rather
+    /// than rendering it inline.
+    async fn export_user_data(&self, claims: Claims) -> ExportUserDataResponse;
+
+    /// Deletes `claims`'s account for `DELETE /api/user`, gated by the
+    /// router on a `?confirm=true` query parameter so a bare `DELETE`
+    /// (e.g. a misconfigured client, a crawler that deletes unsafely)
+    /// can't destroy an account by accident. `password`, if the caller
+    /// supplied one, is whatever re-authentication check the implementor
+    /// wants to run before committing to the delete; implementors that
+    /// don't require one can ignore it.
+    ///
+    /// This crate has no opinion on cascade behavior — whether the
+    /// account's articles, comments, and favorites are deleted with it,
+    /// reassigned to a placeholder account, or left in place with the
+    /// author now unresolvable — beyond noting that whichever policy is
+    /// chosen should be applied consistently, since a dangling
+    /// `author`/`username` reference elsewhere in this crate's models is
+    /// not a case any handler here accounts for.
+    async fn delete_account(&self, claims: Claims, password: Option<String>) -> DeleteAccountResponse;
+
+    /// The current ETag for `claims`'s user profile, if this implementor
+    /// tracks one (e.g. a hash or version counter bumped on every
+    /// `update_user`). When present, the router honors an `If-Match`
+    /// header on `PUT /api/user`, returning
+    /// `UpdateUserResponse::Status412_PreconditionFailed` on a mismatch.
+    /// Defaults to `None`, which skips the check entirely.
+    async fn current_user_etag(&self, claims: &Claims) -> Option<String> {
+        let _ = claims;
+        None
+    }
+
+    /// The caller's current rate-limit window, if this implementor tracks
+    /// one. When present, the router adds `X-RateLimit-Remaining` and
+    /// `X-RateLimit-Reset` headers to an otherwise-successful `login`
+    /// response. Defaults to `None`.
+    fn rate_limit_state(&self) -> Option<RateLimitState> {
+        None
+    }
+
+    /// Whether `identifier` (the submitted username/email, before any
+    /// lockout is applied) is currently locked out of `login` due to too
+    /// many recent failures, checked by the router before calling
+    /// `login` itself — a locked identifier gets
+    /// `LoginResponse::Status423_Locked` without `login` ever running.
+    /// Counting attempts is entirely this method's and
+    /// [`record_login_failure`](Self::record_login_failure)'s
+    /// responsibility, since only the implementor's store can track them
+    /// per identifier; the router only enforces whatever these two
+    /// report. Defaults to `false`, preserving today's behavior until an
+    /// implementor opts in.
+    async fn is_locked(&self, identifier: &str) -> bool {
+        let _ = identifier;
+        false
+    }
+
+    /// Records one failed `login` attempt for `identifier`, called by the
+    /// router right after a `login` call comes back
+    /// `Status401_Unauthorized`. Implementors that want lockout should
+    /// use this to increment their own per-identifier counter (and reset
+    /// it on a successful login) — see
+    /// [`is_locked`](Self::is_locked). Defaults to doing nothing.
+    async fn record_login_failure(&self, identifier: &str) {
+        let _ = identifier;
+    }
+
+    /// Validates a registration payload before it reaches `register`.
+    /// Returns the messages to surface in a `422` response body, or an
+    /// empty vec if the payload is acceptable.
+    ///
+    /// Note that `new_user.username` as received is not yet normalized;
+    /// call [`normalize_username`] before persisting or comparing it, so
+    /// visually-identical usernames typed with different Unicode
+    /// representations can't collide or evade uniqueness checks.
+    fn create_user_validation(&self, new_user: &NewUser) -> Vec<String> {
+        let mut errors = Vec::new();
+        if new_user.username.trim().is_empty() {
+            errors.push("username can't be blank".to_string());
+        }
+        if new_user.email.trim().is_empty() {
+            errors.push("email can't be blank".to_string());
+        }
+        if new_user.password.chars().count() < 8 {
+            errors.push("password is too short (minimum is 8 characters)".to_string());
+        }
+        #[cfg(feature = "password-strength")]
+        if let Err(message) = check_password_strength(&new_user.password, self.min_password_score()) {
+            errors.push(message);
+        }
+        errors
+    }
+
+    /// Whether `email` already belongs to some other user, checked by the
+    /// router before an `UpdateUser` payload that changes the email
+    /// reaches `update_user`. `claims` identifies the caller, so
+    /// implementors can exclude the caller's own current email from the
+    /// check. Defaults to `false`, i.e. no uniqueness enforcement —
+    /// implementors backed by a database with a unique constraint can
+    /// leave this as-is and rely on `update_user` itself rejecting the
+    /// write, or override it here to surface a friendlier `422` up front.
+    fn email_is_taken(&self, claims: &Claims, email: &str) -> bool {
+        let _ = (claims, email);
+        false
+    }
+
+    /// Minimum acceptable zxcvbn score (`0`-`4`) for new and changed
+    /// passwords. Only consulted when the `password-strength` feature is
+    /// enabled. Defaults to `2` ("somewhat guessable").
+    #[cfg(feature = "password-strength")]
+    fn min_password_score(&self) -> u8 {
+        2
+    }
+
+    /// Validates an update payload before it reaches `update_user`, the way
+    /// `create_user_validation` guards `register`. Only the password field
+    /// is checked, since it's the only one `UpdateUser` shares with
+    /// `NewUser`'s strength requirement.
+    #[cfg(feature = "password-strength")]
+    fn update_user_validation(&self, update: &UpdateUser) -> Vec<String> {
+        let mut errors = Vec::new();
+        if let Some(password) = &update.password {
+            if let Err(message) = check_password_strength(password, self.min_password_score()) {
+                errors.push(message);
+            }
+        }
+        errors
+    }
+}
+
+/// Normalizes a username to Unicode NFKC form and trims surrounding
+/// whitespace, so that e.g. `"ｅｖａｎ"` (fullwidth) and `"evan"` are treated
+/// as the same username by uniqueness checks and lookups. Call this on
+/// every incoming username before comparing or persisting it.
+pub fn normalize_username(username: &str) -> String {
+    use unicode_normalization::UnicodeNormalization;
+    username.trim().nfkc().collect()
+}
+
+/// Rejects `password` if its estimated zxcvbn score is below `min_score`.
+/// Returns a descriptive message suitable for a `422` error body.
+#[cfg(feature = "password-strength")]
+fn check_password_strength(password: &str, min_score: u8) -> Result<(), String> {
+    let Ok(estimate) = zxcvbn::zxcvbn(password, &[]) else {
+        // zxcvbn only errors on an empty password; `create_user_validation`
+        // already rejects those via its own blank check.
+        return Ok(());
+    };
+    if estimate.score() < min_score {
+        return Err("password is too weak; try a longer or less predictable phrase".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_fullwidth_to_ascii() {
+        assert_eq!(normalize_username("  ｅｖａｎ  "), "evan");
+    }
+}
@@ -0,0 +1,28 @@
+//! Outbound notifications for article lifecycle events.
+
+use async_trait::async_trait;
+
+use crate::models::Article;
+
+/// An article lifecycle event, dispatched after the corresponding
+/// `Articles` trait method has already succeeded.
+#[derive(Debug, Clone)]
+pub enum ArticleEvent {
+    Created(Article),
+    Updated(Article),
+    Deleted { slug: String },
+}
+
+#[async_trait]
+pub trait WebhookDispatcher {
+    /// Notified after `create_article`/`update_article`/`delete_article`
+    /// succeeds, for implementors that want to notify integrations of
+    /// article changes. Defaults to doing nothing. Delivery (and
+    /// retrying a failed delivery) is entirely this method's
+    /// responsibility — it returns nothing, so the router can't react
+    /// to a delivery failure and doesn't fail the original request over
+    /// one.
+    async fn dispatch(&self, event: ArticleEvent) {
+        let _ = event;
+    }
+}
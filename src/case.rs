@@ -0,0 +1,75 @@
+//! Runtime JSON key-casing, controlled by
+//! [`crate::context::ServerConfig::json_case`].
+//!
+//! The wire models in [`crate::models`] are defined once in `snake_case`
+//! and serialized normally; when camelCase is requested we re-key the
+//! resulting `serde_json::Value` rather than maintaining two sets of
+//! `#[serde(rename_all)]` structs.
+
+use serde_json::Value;
+
+/// Which case convention response bodies use for JSON object keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonCase {
+    /// `snake_case`, matching the model field names as written. Default.
+    #[default]
+    SnakeCase,
+    /// `camelCase`, for frontend ecosystems that expect it.
+    CamelCase,
+}
+
+/// Recursively re-keys every object in `value` from `snake_case` to
+/// `camelCase`, including objects nested inside arrays. Leaves scalar
+/// values untouched.
+pub fn to_camel_case(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, v)| (snake_to_camel(&key), to_camel_case(v)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(to_camel_case).collect()),
+        other => other,
+    }
+}
+
+fn snake_to_camel(key: &str) -> String {
+    let mut result = String::with_capacity(key.len());
+    let mut upper_next = false;
+    for c in key.chars() {
+        if c == '_' {
+            upper_next = true;
+        } else if upper_next {
+            result.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Serializes `value` according to `case`, as a ready-to-send response
+/// body string.
+pub fn render(value: impl serde::Serialize, case: JsonCase) -> String {
+    let value = serde_json::to_value(value).unwrap_or_default();
+    let value = match case {
+        JsonCase::SnakeCase => value,
+        JsonCase::CamelCase => to_camel_case(value),
+    };
+    serde_json::to_string(&value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn camel_cases_nested_keys() {
+        let input = json!({"tag_list": ["dragons"], "author": {"favorites_count": 3}});
+        let output = to_camel_case(input);
+        assert_eq!(output["tagList"], json!(["dragons"]));
+        assert_eq!(output["author"]["favoritesCount"], 3);
+    }
+}
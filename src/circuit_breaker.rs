@@ -0,0 +1,133 @@
+//! A small failure-count circuit breaker.
+//!
+//! This crate can't see failures inside an implementor's trait method
+//! bodies — every `apis` trait method returns an infallible `*Response`
+//! enum, not a `Result`, so there's no failure signal at the router layer
+//! to trip a breaker on. [`CircuitBreaker`] is offered as a primitive for
+//! implementors to embed in their own state and use around whatever
+//! actually fails on their side (a database call, an upstream HTTP
+//! request) inside a trait method body, then map an open breaker to
+//! whichever `*Response` variant fits (usually something in the 5xx
+//! range, via the enum's existing variants or a new one).
+//!
+//! With the `circuit-breaker` feature enabled, an implementor can also
+//! hand the router the same breaker via
+//! [`crate::context::ServerConfig::circuit_breaker`]: the router checks
+//! `is_open` before calling into any mutating handler's trait method and
+//! returns `503 Service Unavailable` itself when it's tripped, so a
+//! failing backing store doesn't keep taking mutating requests it's
+//! certain to fail. Recording outcomes is still the implementor's job,
+//! for the reason above.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct Inner {
+    failure_threshold: u32,
+    reset_after: Duration,
+    consecutive_failures: AtomicU32,
+    opened_at: Mutex<Option<Instant>>,
+}
+
+/// Trips open after `failure_threshold` consecutive [`record_failure`]
+/// calls, then refuses calls (per [`is_open`]) until `reset_after` has
+/// elapsed, at which point it goes half-open: the next [`is_open`] call
+/// returns `false` again, and whichever of `record_success`/
+/// `record_failure` the caller reports next decides whether it stays
+/// closed or re-opens.
+///
+/// Cheap to [`Clone`] — every clone shares the same underlying counters —
+/// so it can be stored directly on application state and handed out to
+/// trait method implementations that need it.
+///
+/// [`record_failure`]: CircuitBreaker::record_failure
+/// [`is_open`]: CircuitBreaker::is_open
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    inner: Arc<Inner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, reset_after: Duration) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                failure_threshold,
+                reset_after,
+                consecutive_failures: AtomicU32::new(0),
+                opened_at: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Whether calls should currently be short-circuited instead of
+    /// reaching the real downstream call.
+    pub fn is_open(&self) -> bool {
+        let mut opened_at = self.inner.opened_at.lock().unwrap();
+        match *opened_at {
+            Some(at) if at.elapsed() < self.inner.reset_after => true,
+            Some(_) => {
+                *opened_at = None;
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Resets the consecutive-failure count and closes the breaker if it
+    /// was open.
+    pub fn record_success(&self) {
+        self.inner.consecutive_failures.store(0, Ordering::Relaxed);
+        *self.inner.opened_at.lock().unwrap() = None;
+    }
+
+    /// Counts one more consecutive failure, opening the breaker once
+    /// `failure_threshold` is reached.
+    pub fn record_failure(&self) {
+        let failures = self.inner.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures >= self.inner.failure_threshold {
+            *self.inner.opened_at.lock().unwrap() = Some(Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stays_closed_under_the_failure_threshold() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_failure();
+        assert!(!breaker.is_open());
+    }
+
+    #[test]
+    fn opens_once_the_failure_threshold_is_reached() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_failure();
+        breaker.record_failure();
+        assert!(breaker.is_open());
+    }
+
+    #[test]
+    fn a_success_resets_the_failure_count_and_closes_the_breaker() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        assert!(!breaker.is_open());
+    }
+
+    #[test]
+    fn goes_half_open_once_reset_after_has_elapsed() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(1));
+        breaker.record_failure();
+        assert!(breaker.is_open());
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(!breaker.is_open());
+    }
+}
@@ -0,0 +1,88 @@
+//! A per-key concurrency limiter, for implementors that want to cap how
+//! many in-flight operations a single key (e.g. a user id) can have at
+//! once without reaching for an external rate limiter.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Lazily creates a [`tokio::sync::Semaphore`] per key the first time it's
+/// acquired, then reuses it for later acquisitions of the same key.
+/// Cheap to [`Clone`] — clones share the same underlying map.
+#[derive(Debug, Clone)]
+pub struct PerKeyLimiter {
+    permits_per_key: usize,
+    semaphores: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+}
+
+impl PerKeyLimiter {
+    /// `permits_per_key` is the maximum number of concurrent
+    /// [`acquire`](Self::acquire) calls allowed for any single key at
+    /// once; further calls for that key wait until one is released.
+    pub fn new(permits_per_key: usize) -> Self {
+        Self {
+            permits_per_key,
+            semaphores: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Waits for a free permit under `key`, returning a guard that
+    /// releases it on drop.
+    pub async fn acquire(&self, key: &str) -> OwnedSemaphorePermit {
+        self.semaphore_for(key)
+            .acquire_owned()
+            .await
+            .expect("semaphore is never closed")
+    }
+
+    /// Like [`acquire`](Self::acquire), but returns `None` immediately
+    /// instead of waiting if `key` is already at its permit limit —
+    /// for callers that want to reject a caller outright (e.g. with
+    /// `429 Too Many Requests`) rather than queue it.
+    pub fn try_acquire(&self, key: &str) -> Option<OwnedSemaphorePermit> {
+        self.semaphore_for(key).try_acquire_owned().ok()
+    }
+
+    fn semaphore_for(&self, key: &str) -> Arc<Semaphore> {
+        let mut semaphores = self.semaphores.lock().unwrap();
+        semaphores
+            .entry(key.to_string())
+            .or_insert_with(|| Arc::new(Semaphore::new(self.permits_per_key)))
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_acquire_succeeds_under_the_limit() {
+        let limiter = PerKeyLimiter::new(1);
+        assert!(limiter.try_acquire("user-1").is_some());
+    }
+
+    #[test]
+    fn try_acquire_fails_once_a_key_is_at_its_limit() {
+        let limiter = PerKeyLimiter::new(1);
+        let permit = limiter.try_acquire("user-1");
+        assert!(permit.is_some());
+        assert!(limiter.try_acquire("user-1").is_none());
+    }
+
+    #[test]
+    fn try_acquire_tracks_each_key_independently() {
+        let limiter = PerKeyLimiter::new(1);
+        let _permit = limiter.try_acquire("user-1");
+        assert!(limiter.try_acquire("user-2").is_some());
+    }
+
+    #[test]
+    fn try_acquire_succeeds_again_once_a_permit_is_dropped() {
+        let limiter = PerKeyLimiter::new(1);
+        let permit = limiter.try_acquire("user-1");
+        drop(permit);
+        assert!(limiter.try_acquire("user-1").is_some());
+    }
+}
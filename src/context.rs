@@ -0,0 +1,525 @@
+//! Request-scoped context and server-wide configuration.
+//!
+//! `Claims` is whatever the implementor's auth layer decodes from the
+//! bearer token; this crate never inspects it beyond passing it through to
+//! trait methods. `ServerConfig` holds process-wide, implementor-supplied
+//! settings that affect how responses are built.
+
+/// Opaque bearer-token claims, threaded from the `Authorization` header into
+/// trait methods that require an authenticated caller. The implementor
+/// decides what goes in here; this crate just carries it.
+#[derive(Debug, Clone)]
+pub struct Claims {
+    pub user_id: String,
+    pub username: String,
+}
+
+/// A source of the current time, injected via [`ServerConfig::clock`] so
+/// timestamp-sensitive checks can be tested deterministically instead of
+/// depending on the system clock. Implementors with their own
+/// time-reading logic (outside what this crate checks itself) are free
+/// to consult the same [`ServerConfig::clock`] rather than calling
+/// `Utc::now()` directly, for the same reason.
+pub trait Clock: std::fmt::Debug + Send + Sync {
+    fn now(&self) -> chrono::DateTime<chrono::Utc>;
+}
+
+/// The default [`Clock`]: reads the real system time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc::now()
+    }
+}
+
+/// A [`Clock`] that always returns the same fixed instant, for
+/// deterministic tests of timestamp-sensitive behavior.
+#[derive(Debug, Clone, Copy)]
+pub struct MockClock(pub chrono::DateTime<chrono::Utc>);
+
+impl Clock for MockClock {
+    fn now(&self) -> chrono::DateTime<chrono::Utc> {
+        self.0
+    }
+}
+
+/// Rate-limit counters for the caller's current window, as reported by an
+/// implementor tracking its own limiter. Handlers that receive one of
+/// these translate it into `X-RateLimit-*` headers on an otherwise
+/// successful response; implementors that don't track rate limits simply
+/// never return one.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitState {
+    pub limit: u64,
+    pub remaining: u64,
+    /// Unix timestamp (seconds) at which `remaining` resets to `limit`.
+    pub reset: u64,
+}
+
+/// Process-wide configuration controlling cross-cutting response behavior.
+/// Constructed once at startup and shared (via `Arc` or `Clone`) with the
+/// router.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Minimum password length. Not read by the default
+    /// `create_user_validation`, which applies its own fixed 8-character
+    /// minimum.
+    pub min_password_length: usize,
+    /// Minimum acceptable [zxcvbn](https://docs.rs/zxcvbn) score (`0`-`4`)
+    /// for new and changed passwords. Only present when the
+    /// `password-strength` feature is enabled. Not read by the default
+    /// validators, which take their threshold from
+    /// [`crate::apis::users::Users::min_password_score`] instead.
+    #[cfg(feature = "password-strength")]
+    pub min_password_score: u8,
+    /// Wrap successful single-resource responses in the [JSON:API](https://jsonapi.org)
+    /// envelope (`{"data": {"type": ..., "id": ..., "attributes": {...}}}`)
+    /// with `Content-Type: application/vnd.api+json`, instead of the plain
+    /// Conduit shape. Off by default.
+    pub json_api: bool,
+    /// When set, requests whose `Host` header isn't in this list are
+    /// rejected with `400 Bad Request` by the
+    /// [`crate::middleware::allowed_hosts`] layer. `None` allows any host.
+    pub allowed_hosts: Option<Vec<String>>,
+    /// Gates whether the `login` handler consults
+    /// [`crate::apis::users::Users::is_locked`]/
+    /// [`crate::apis::users::Users::record_login_failure`] at all. Those
+    /// two methods do the actual counting and enforce their own
+    /// threshold against whatever identifier they're passed — this flag
+    /// only controls whether the router bothers calling them, letting a
+    /// deployment that hasn't implemented lockout skip the extra calls
+    /// entirely. `None` (the default) disables lockout.
+    pub max_login_attempts: Option<u32>,
+    /// Whether [`crate::middleware::strip_response_headers`] runs. On by
+    /// default; an implementor serving behind a proxy that already
+    /// scrubs these headers can turn it off to save the per-request work.
+    pub strip_response_headers: bool,
+    /// How `favorite_article` should behave when a caller favorites an
+    /// article they've already favorited (e.g. two in-flight requests
+    /// racing). This crate doesn't enforce either policy itself — it's
+    /// documentation for implementors plus a place for them to read a
+    /// deployment's configured choice from.
+    pub duplicate_favorite_policy: DuplicateFavoritePolicy,
+    /// Include a `links` object (`self`/`next`/`prev`/`first`/`last`) in
+    /// list responses, built from the request's `limit`/`offset`. Off by
+    /// default to keep the response shape unchanged for existing clients.
+    pub pagination_links: bool,
+    /// Maximum nesting depth allowed in a request body, enforced by
+    /// [`crate::middleware::max_json_depth`]. `None` disables the check.
+    pub max_json_depth: Option<usize>,
+    /// Serve a Swagger UI page at `GET /docs`, pointed at `/api/openapi.json`.
+    /// Off by default.
+    pub swagger_ui: bool,
+    /// JSON key casing used for response bodies. Defaults to `SnakeCase`,
+    /// matching the model field names as written; set to `CamelCase` for
+    /// frontend ecosystems that expect it. Applied to the article list,
+    /// drafts, change-feed, and user-export responses, and to the
+    /// single-article responses of `GET`/`PUT /api/articles/:slug` and
+    /// favorite/unfavorite; every other response (including
+    /// `create_article`'s) is serialized with the field names as written.
+    pub json_case: crate::case::JsonCase,
+    /// Body served for `GET /`. `None` (the default) leaves the route
+    /// unregistered, so it 404s like any other unknown path. Useful for a
+    /// health-check or "which API is this" landing response without
+    /// standing up a whole static-file server.
+    pub root_response: Option<serde_json::Value>,
+    /// Maximum request body size in bytes, enforced by
+    /// [`crate::middleware::request_body_limit`] as the body streams in
+    /// off the wire (including past any chunked `Transfer-Encoding`
+    /// framing, which hyper decodes transparently before this crate ever
+    /// sees the bytes). This crate never transparently decompresses
+    /// request bodies, so this limit is also the decompression-bomb
+    /// guard: there's no inflated-size stage to sneak a small payload
+    /// past. Defaults to 2 MiB.
+    pub max_request_body_bytes: usize,
+    /// A larger request body limit applied only to `POST /api/articles`
+    /// and `PUT /api/articles/:slug`, for deployments that want most
+    /// endpoints capped tightly but need to accept long-form article
+    /// bodies. `None` (the default) leaves both routes under the blanket
+    /// `max_request_body_bytes` limit like everything else.
+    ///
+    /// This doesn't change how the body is *parsed* — `serde_json` still
+    /// buffers and deserializes it in one pass — only how large a buffered
+    /// body axum will accept before rejecting the request.
+    pub max_article_body_bytes: Option<usize>,
+    /// Whether a query string that fails to deserialize (e.g. `limit=abc`
+    /// where a number is expected) is rejected outright with `400 Bad
+    /// Request` (`true`, the default) or silently treated as if the
+    /// offending parameters were omitted, falling back to their defaults
+    /// (`false`). Lenient mode trades strictness for tolerating clients
+    /// that send garbage values instead of just leaving a param off.
+    pub query_param_strict: bool,
+    /// Route patterns (matched the same way as
+    /// [`ServerConfig::deprecated_routes`]) that are rejected with `503
+    /// Service Unavailable` instead of reaching their handler. Lets an
+    /// operator pull a misbehaving endpoint out of rotation without a
+    /// redeploy. Empty by default.
+    pub disabled_routes: Vec<String>,
+    /// Run `create_user_validation`/`update_user_validation` on a
+    /// `tokio::task::spawn_blocking` thread instead of inline on the
+    /// async task handling the request. Matters most with the
+    /// `password-strength` feature enabled, since zxcvbn's estimate is
+    /// CPU-bound enough to matter on a busy executor. On by default;
+    /// turn off for deployments where the extra thread hop costs more
+    /// than the validation itself (e.g. `password-strength` disabled, so
+    /// there's nothing CPU-heavy to offload).
+    pub offload_validation: bool,
+    /// Logs a `tracing::warn!` if a request takes longer than this to
+    /// complete, tagged with the route and elapsed time. Meant to surface
+    /// an implementor trait method that's hung (e.g. deadlocked on its
+    /// own lock, or waiting on a connection pool that's exhausted) —
+    /// this crate doesn't cancel the request itself, just reports it.
+    /// `None` (the default) disables the check.
+    pub max_request_duration_warning: Option<std::time::Duration>,
+    /// Caps the number of `favorite_article`/`unfavorite_article` calls a
+    /// single user may have in flight at once, keyed on `claims.user_id`.
+    /// The router calls
+    /// [`try_acquire`](crate::concurrency::PerKeyLimiter::try_acquire)
+    /// before reaching either trait method and returns `429 Too Many
+    /// Requests` immediately if the caller's permits are already
+    /// exhausted, rather than queuing the request. Since the limiter has
+    /// to track in-flight calls across requests, not just within one,
+    /// construct it once (e.g.
+    /// `PerKeyLimiter::new(5)`) and keep returning the same clone from
+    /// [`HasServerConfig::server_config`], the same way
+    /// [`ServerConfig::clock`] is meant to be shared rather than rebuilt
+    /// per call. `None` (the default) disables the limit.
+    pub max_concurrent_favorites_per_user: Option<crate::concurrency::PerKeyLimiter>,
+    /// Serve [`crate::introspection::list_routes`] at `GET /api/routes`,
+    /// a hand-maintained list of every route this crate registers. Off by
+    /// default, since exposing your own route table is rarely something
+    /// you want a stranger to be able to fetch.
+    pub route_introspection: bool,
+    /// Route patterns (as registered in [`crate::router::build_router`],
+    /// e.g. `"/api/articles/:slug/export"`) that get a `Deprecation: true`
+    /// response header, per the [draft-ietf-httpapi-deprecation-header]
+    /// convention. Matched against axum's `MatchedPath`, so path
+    /// parameters don't need to be filled in. Empty by default.
+    ///
+    /// [draft-ietf-httpapi-deprecation-header]: https://datatracker.ietf.org/doc/draft-ietf-httpapi-deprecation-header/
+    pub deprecated_routes: Vec<String>,
+    /// Whether non-fatal validation warnings (e.g.
+    /// [`crate::apis::tags::Tags::tag_warnings`]) are surfaced to the
+    /// client at all. When off (the default), the router computes
+    /// warnings nowhere and an otherwise-valid request's response is
+    /// unchanged; when on, they're added to the response's `Warning`
+    /// header per RFC 7234 §5.5, one header value per warning.
+    pub validation_warnings: bool,
+    /// Whether `GET /api/stats` answers anonymous requests. When `true`
+    /// (the default), anyone can fetch aggregate counts; when `false`,
+    /// the router requires an authenticated caller, returning `401` for
+    /// anonymous requests before `Stats::get_stats` is ever called.
+    pub public_stats: bool,
+    /// Value of the `X-API-Version` header added to every response.
+    /// Defaults to this crate's own `Cargo.toml` version; override for a
+    /// deployment-specific build identifier (e.g. a git SHA) instead.
+    pub api_version: String,
+    /// When set, [`crate::router::build_router`] logs a `tracing::warn!`
+    /// if an outgoing article or comment's `created_at`/`updated_at` is
+    /// further in the future than this tolerance allows for clock skew
+    /// between this process and whatever persisted the timestamp. A
+    /// response is never rejected over it — this only surfaces an
+    /// implementor bug (a bad clock, a timezone mix-up) during
+    /// development or in logs. `None` (the default) disables the check.
+    pub strict_timestamps: Option<std::time::Duration>,
+    /// When `true`, `update_article`, `delete_article`, and `update_user`
+    /// reject a request with `428 Precondition Required` unless it
+    /// carries an `If-Match` header, before the trait method ever runs.
+    /// Guards against a client overwriting or deleting a resource it
+    /// never actually read first (and so can't know it's clobbering).
+    /// Off by default, since it requires every client to send
+    /// conditional requests.
+    pub require_conditional_writes: bool,
+    /// Run [`crate::middleware::detailed_json_errors`] over every request
+    /// body, reporting a JSON syntax error's byte offset in a
+    /// [`crate::models::GenericErrorModel`] instead of axum's default
+    /// rejection body. Off by default, since it costs an extra parse
+    /// pass over every request.
+    pub detailed_json_errors: bool,
+    /// When set, `create_article` and `rename_tag` reject any tag not in
+    /// this set with `422 Unprocessable Entity`, naming the disallowed
+    /// tags. `None` (the default) allows any tag that otherwise passes
+    /// [`crate::apis::tags::Tags::tag_validation`].
+    pub allowed_tags: Option<std::collections::HashSet<String>>,
+    /// How `create_article` handles a `NewArticle.tag_list` containing the
+    /// same tag twice (e.g. `["rust", "rust"]`). When `true`, the request
+    /// is rejected with `422 Unprocessable Entity` naming each duplicated
+    /// tag, same as any other `create_article_validation` failure. When
+    /// `false` (the default), duplicates are silently removed — keeping
+    /// the first occurrence's position — before the tag list ever reaches
+    /// [`crate::apis::articles::Articles::create_article_validation`] or
+    /// `create_article` itself, so implementors never see one.
+    pub reject_duplicate_tags: bool,
+    /// Plain-text body served for `GET /robots.txt`. `None` (the
+    /// default) leaves the route unregistered. Mirrors
+    /// [`ServerConfig::root_response`] for a second commonly-expected
+    /// static route.
+    pub robots_txt: Option<String>,
+    /// Plain-text body served for `GET /.well-known/security.txt`, per
+    /// [RFC 9116](https://www.rfc-editor.org/rfc/rfc9116). `None` (the
+    /// default) leaves the route unregistered.
+    pub security_txt: Option<String>,
+    /// Substituted for [`crate::models::Profile::image`] whenever it's
+    /// empty, on every response that serializes a `Profile` (directly,
+    /// or nested in an `Article`'s or `Comment`'s `author`). `None` (the
+    /// default) leaves an empty `image` as-is, preserving today's
+    /// behavior of pushing the fallback-avatar decision onto clients.
+    pub default_avatar_url: Option<String>,
+    /// Whether `GET /api/user/export` honors a `Range` header, serving
+    /// `206 Partial Content` plus `Accept-Ranges: bytes` like `GET
+    /// /api/articles/:slug/export` always does, so a client resuming an
+    /// interrupted download doesn't have to restart from the top. Off by
+    /// default: the whole export is buffered in memory either way (this
+    /// crate has no streaming export path), so enabling it doesn't change
+    /// memory use — only whether a client's `Range` header is honored.
+    pub range_requests_on_user_export: bool,
+    /// Maximum size, in bytes, of a serialized Conduit-shaped response
+    /// body. A response exceeding this is a bug, not a client error
+    /// (typically an implementor returning an unbounded list), so the
+    /// router logs it with `tracing::error!` and returns `500 Internal
+    /// Server Error` rather than serving a partial or oversized body.
+    /// `None` (the default) leaves responses unbounded. Mirrors
+    /// [`ServerConfig::max_request_body_bytes`] for the opposite
+    /// direction. Checked on the same responses that honor
+    /// [`ServerConfig::json_case`]; the other responses aren't size-checked.
+    pub max_response_bytes: Option<usize>,
+    /// Maximum number of comments a single article may accumulate. Passed
+    /// through to [`crate::apis::comments::Comments::add_comment`], which
+    /// returns [`crate::apis::comments::AddCommentResponse::Status409_Conflict`]
+    /// once the cap is reached — this crate doesn't count an article's
+    /// existing comments itself, so enforcement is the implementor's call
+    /// to make with the limit in hand. `None` (the default) leaves articles
+    /// uncapped.
+    pub max_comments_per_article: Option<usize>,
+    /// Route patterns (matched the same way as
+    /// [`ServerConfig::deprecated_routes`]) mapped to the `Cache-Control`
+    /// directive served for that route, e.g. `("/api/articles",
+    /// "max-age=30, stale-while-revalidate=60".to_string())` for a feed
+    /// that's fine being briefly stale while a background refetch catches
+    /// up. A request carrying an `Authorization` header or `token` cookie
+    /// gets `Cache-Control: private` instead, regardless of this map, since
+    /// a response that might be personalized isn't safe for a shared cache
+    /// to reuse across users — this middleware runs before any trait
+    /// method resolves whether the credential is actually valid, so it
+    /// errs toward `private` on the mere presence of a credential. Empty
+    /// by default, which adds no `Cache-Control` header at all.
+    pub cache_control_routes: Vec<(String, String)>,
+    /// How the router resolves a request carrying both an `Authorization`
+    /// header and a `token` cookie, for implementors that accept both. See
+    /// [`ConflictingAuthAction`]. Defaults to
+    /// [`ConflictingAuthAction::PreferHeader`].
+    pub conflicting_auth_action: ConflictingAuthAction,
+    /// Source of "now" for every timestamp-sensitive check the router
+    /// runs itself (currently just [`ServerConfig::strict_timestamps`]'s
+    /// future-date guard). Defaults to [`SystemClock`]; swap in a
+    /// [`MockClock`] in tests that need a deterministic "now" instead of
+    /// depending on wall-clock time.
+    pub clock: std::sync::Arc<dyn Clock>,
+    /// Rejects a request with `403 Forbidden` if it carries an
+    /// `X-Forwarded-Proto` header naming anything other than `https`, via
+    /// [`crate::middleware::require_https`]. This crate sits behind
+    /// whatever proxy terminates TLS, so it has no direct way to observe
+    /// the original connection's scheme — enabling this means trusting
+    /// that proxy to set `X-Forwarded-Proto` accurately, the same trust
+    /// already implied by relying on it at all. A request with no
+    /// `X-Forwarded-Proto` header is let through either way, since its
+    /// absence isn't by itself evidence of plaintext (e.g. a proxy that
+    /// only forwards the header on an explicit HTTP fallback). Every
+    /// response gets a `Strict-Transport-Security` header while this is
+    /// on, regardless of scheme. Off by default.
+    pub require_https: bool,
+    /// Fraction of requests, in `[0.0, 1.0]`, that
+    /// [`crate::middleware::log_sampler`] logs at `info` with the
+    /// request's route, status, and latency. Sampling is deterministic
+    /// per request — keyed off an incoming `X-Request-Id` header when one
+    /// is sent (a request without one is keyed off a fresh id of the
+    /// sampler's own) — so a request that's logged once would be logged again on
+    /// a retry carrying the same `X-Request-Id`, and a high-traffic
+    /// deployment gets a representative sample rather than every
+    /// request's log line. `0.0` logs nothing; `1.0` (the default) logs
+    /// everything, matching today's behavior.
+    pub log_sample_rate: f64,
+    /// Shared [`CircuitBreaker`](crate::circuit_breaker::CircuitBreaker)
+    /// handle consulted by the router before running any mutating
+    /// handler's trait method: an open breaker short-circuits the
+    /// request with `503 Service Unavailable` instead. This crate has no
+    /// way to see whether a trait method's own call into the implementor's
+    /// store succeeded, so recording outcomes is still the implementor's
+    /// job — they hold (or clone) the same `CircuitBreaker` in their own
+    /// state and call `record_success`/`record_failure` around whatever
+    /// actually talks to their backing store; this field just gives the
+    /// router a handle onto the same breaker to check before it bothers
+    /// calling in. `None` (the default) never short-circuits anything.
+    /// Only present with the `circuit-breaker` feature enabled.
+    #[cfg(feature = "circuit-breaker")]
+    pub circuit_breaker: Option<crate::circuit_breaker::CircuitBreaker>,
+}
+
+/// See [`ServerConfig::conflicting_auth_action`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictingAuthAction {
+    /// When both credentials are present and valid but resolve to
+    /// different users, the `Authorization` header wins silently — the
+    /// cookie is ignored. This is the default, since it's what most
+    /// clients expect from header/cookie precedence elsewhere (e.g.
+    /// browsers favoring explicit request headers over ambient cookies).
+    #[default]
+    PreferHeader,
+    /// When both credentials are present and valid but resolve to
+    /// different users, the request is rejected with `400 Bad Request`
+    /// rather than silently picking one, for deployments that consider
+    /// disagreeing credentials a sign of a misbehaving or compromised
+    /// client worth surfacing rather than papering over.
+    Reject,
+}
+
+/// See [`ServerConfig::duplicate_favorite_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateFavoritePolicy {
+    /// Repeated favorites from the same user are a no-op:
+    /// `favorites_count` only increments on the first one. This is the
+    /// default, and matches the Conduit spec's implied semantics.
+    #[default]
+    Idempotent,
+    /// Every `favorite_article` call increments `favorites_count`, even
+    /// from the same user. Opt-in for implementors that want a "likes"
+    /// style counter instead of a set of favoriters.
+    Increment,
+}
+
+impl ServerConfig {
+    pub fn new() -> Self {
+        Self {
+            min_password_length: 8,
+            #[cfg(feature = "password-strength")]
+            min_password_score: 2,
+            json_api: false,
+            allowed_hosts: None,
+            max_login_attempts: None,
+            strip_response_headers: true,
+            duplicate_favorite_policy: DuplicateFavoritePolicy::default(),
+            pagination_links: false,
+            max_json_depth: None,
+            swagger_ui: false,
+            json_case: crate::case::JsonCase::default(),
+            root_response: None,
+            max_request_body_bytes: 2 * 1024 * 1024,
+            route_introspection: false,
+            deprecated_routes: Vec::new(),
+            max_article_body_bytes: None,
+            query_param_strict: true,
+            disabled_routes: Vec::new(),
+            offload_validation: true,
+            max_request_duration_warning: None,
+            max_concurrent_favorites_per_user: None,
+            validation_warnings: false,
+            public_stats: true,
+            api_version: env!("CARGO_PKG_VERSION").to_string(),
+            strict_timestamps: None,
+            require_conditional_writes: false,
+            detailed_json_errors: false,
+            allowed_tags: None,
+            reject_duplicate_tags: false,
+            robots_txt: None,
+            security_txt: None,
+            default_avatar_url: None,
+            range_requests_on_user_export: false,
+            max_response_bytes: None,
+            max_comments_per_article: None,
+            cache_control_routes: Vec::new(),
+            conflicting_auth_action: ConflictingAuthAction::default(),
+            clock: std::sync::Arc::new(SystemClock),
+            require_https: false,
+            log_sample_rate: 1.0,
+            #[cfg(feature = "circuit-breaker")]
+            circuit_breaker: None,
+        }
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            min_password_length: 0,
+            #[cfg(feature = "password-strength")]
+            min_password_score: 0,
+            json_api: false,
+            allowed_hosts: None,
+            max_login_attempts: None,
+            strip_response_headers: false,
+            duplicate_favorite_policy: DuplicateFavoritePolicy::default(),
+            pagination_links: false,
+            max_json_depth: None,
+            swagger_ui: false,
+            json_case: crate::case::JsonCase::default(),
+            root_response: None,
+            max_request_body_bytes: 0,
+            route_introspection: false,
+            deprecated_routes: Vec::new(),
+            max_article_body_bytes: None,
+            query_param_strict: false,
+            disabled_routes: Vec::new(),
+            offload_validation: false,
+            max_request_duration_warning: None,
+            max_concurrent_favorites_per_user: None,
+            validation_warnings: false,
+            public_stats: false,
+            api_version: String::new(),
+            strict_timestamps: None,
+            require_conditional_writes: false,
+            detailed_json_errors: false,
+            allowed_tags: None,
+            reject_duplicate_tags: false,
+            robots_txt: None,
+            security_txt: None,
+            default_avatar_url: None,
+            range_requests_on_user_export: false,
+            max_response_bytes: None,
+            max_comments_per_article: None,
+            cache_control_routes: Vec::new(),
+            conflicting_auth_action: ConflictingAuthAction::default(),
+            clock: std::sync::Arc::new(SystemClock),
+            require_https: false,
+            log_sample_rate: 1.0,
+            #[cfg(feature = "circuit-breaker")]
+            circuit_breaker: None,
+        }
+    }
+}
+
+/// Implemented by application state types that carry a [`ServerConfig`],
+/// so the router can look up cross-cutting settings without every trait
+/// method needing its own config parameter. Defaults to
+/// `ServerConfig::new()` for implementors that don't opt in — the
+/// thoughtful defaults (e.g. a 2 MiB request body cap), not
+/// `ServerConfig::default()`'s all-zeroed values, which exist for tests
+/// that want every optional guard off rather than for an implementor who
+/// never configured anything at all.
+pub trait HasServerConfig {
+    fn server_config(&self) -> ServerConfig {
+        ServerConfig::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mock_clock_always_returns_its_fixed_instant() {
+        let fixed = "2024-01-01T00:00:00Z".parse().unwrap();
+        let clock = MockClock(fixed);
+        assert_eq!(clock.now(), fixed);
+        assert_eq!(clock.now(), fixed);
+    }
+
+    #[test]
+    fn server_config_defaults_to_a_system_clock() {
+        let before = chrono::Utc::now();
+        let now = ServerConfig::new().clock.now();
+        let after = chrono::Utc::now();
+        assert!(before <= now && now <= after);
+    }
+}
@@ -0,0 +1,28 @@
+//! Serves a Swagger UI page over the static OpenAPI document, when enabled
+//! via [`crate::context::ServerConfig::swagger_ui`].
+
+use axum::response::{Html, IntoResponse, Response};
+
+const SWAGGER_UI_HTML: &str = r##"<!DOCTYPE html>
+<html>
+  <head>
+    <title>Conduit API docs</title>
+    <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist/swagger-ui.css" />
+  </head>
+  <body>
+    <div id="swagger-ui"></div>
+    <script src="https://unpkg.com/swagger-ui-dist/swagger-ui-bundle.js"></script>
+    <script>
+      window.onload = () => {
+        window.ui = SwaggerUIBundle({
+          url: "/api/openapi.json",
+          dom_id: "#swagger-ui",
+        });
+      };
+    </script>
+  </body>
+</html>"##;
+
+pub async fn swagger_ui() -> Response {
+    Html(SWAGGER_UI_HTML).into_response()
+}
@@ -0,0 +1,69 @@
+//! Route introspection, served at `GET /api/routes` when enabled via
+//! [`crate::context::ServerConfig::route_introspection`].
+//!
+//! This list is maintained by hand alongside [`crate::router::build_router`]
+//! rather than walked off the live `axum::Router` — axum doesn't expose an
+//! API for that, so keeping the two in sync is a review-time discipline,
+//! not something the compiler checks.
+
+use axum::response::{IntoResponse, Json, Response};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteInfo {
+    pub method: &'static str,
+    pub path: &'static str,
+}
+
+const ROUTES: &[RouteInfo] = &[
+    RouteInfo { method: "POST", path: "/api/users/login" },
+    RouteInfo { method: "POST", path: "/api/users" },
+    RouteInfo { method: "GET", path: "/api/users/:username" },
+    RouteInfo { method: "GET", path: "/api/user" },
+    RouteInfo { method: "PUT", path: "/api/user" },
+    RouteInfo { method: "PATCH", path: "/api/user" },
+    RouteInfo { method: "DELETE", path: "/api/user" },
+    RouteInfo { method: "POST", path: "/api/user/token" },
+    RouteInfo { method: "GET", path: "/api/user/export" },
+    RouteInfo { method: "GET", path: "/api/user/drafts" },
+    RouteInfo { method: "GET", path: "/api/articles" },
+    RouteInfo { method: "POST", path: "/api/articles" },
+    RouteInfo { method: "GET", path: "/api/articles/:slug" },
+    RouteInfo { method: "PUT", path: "/api/articles/:slug" },
+    RouteInfo { method: "DELETE", path: "/api/articles/:slug" },
+    RouteInfo { method: "GET", path: "/api/articles/:slug/export" },
+    RouteInfo { method: "GET", path: "/api/articles/:slug/oembed" },
+    RouteInfo { method: "POST", path: "/api/articles/:slug/favorite" },
+    RouteInfo { method: "DELETE", path: "/api/articles/:slug/favorite" },
+    RouteInfo { method: "GET", path: "/api/articles/:slug/comments" },
+    RouteInfo { method: "POST", path: "/api/articles/:slug/comments" },
+    RouteInfo { method: "DELETE", path: "/api/articles/:slug/comments" },
+    RouteInfo { method: "DELETE", path: "/api/articles/:slug/comments/:id" },
+    RouteInfo { method: "GET", path: "/api/comments/recent" },
+    RouteInfo { method: "GET", path: "/api/articles/changes" },
+    RouteInfo { method: "GET", path: "/api/articles/slugify" },
+    RouteInfo { method: "GET", path: "/api/articles/options" },
+    RouteInfo { method: "GET", path: "/api/articles/leaderboard" },
+    RouteInfo { method: "POST", path: "/api/articles/bulk" },
+    RouteInfo { method: "GET", path: "/api/profiles/:username" },
+    RouteInfo { method: "GET", path: "/api/profiles/:username/summary" },
+    RouteInfo { method: "GET", path: "/api/profiles/:username/tags" },
+    RouteInfo { method: "POST", path: "/api/profiles/:username/follow" },
+    RouteInfo { method: "DELETE", path: "/api/profiles/:username/follow" },
+    RouteInfo { method: "PUT", path: "/api/profiles/:username/follow" },
+    RouteInfo { method: "GET", path: "/api/tags" },
+    RouteInfo { method: "GET", path: "/api/tags/popular" },
+    RouteInfo { method: "PUT", path: "/api/tags/:tag" },
+    RouteInfo { method: "DELETE", path: "/api/tags/:tag" },
+    RouteInfo { method: "GET", path: "/api/stats" },
+    RouteInfo { method: "POST", path: "/api/batch" },
+    RouteInfo { method: "GET", path: "/api/routes" },
+    RouteInfo { method: "GET", path: "/docs" },
+    RouteInfo { method: "GET", path: "/" },
+    RouteInfo { method: "GET", path: "/robots.txt" },
+    RouteInfo { method: "GET", path: "/.well-known/security.txt" },
+];
+
+pub async fn list_routes() -> Response {
+    Json(ROUTES).into_response()
+}
@@ -0,0 +1,63 @@
+//! Optional [JSON:API](https://jsonapi.org) envelope, used when
+//! [`crate::context::ServerConfig::json_api`] is enabled.
+
+use axum::http::header::CONTENT_TYPE;
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+pub const CONTENT_TYPE_JSON_API: &str = "application/vnd.api+json";
+
+/// Wraps a single resource as `{"data": {"type", "id", "attributes"}}`.
+pub fn single(resource_type: &str, id: &str, attributes: impl Serialize) -> Value {
+    json!({
+        "data": {
+            "type": resource_type,
+            "id": id,
+            "attributes": attributes,
+        }
+    })
+}
+
+/// Wraps a list of resources as `{"data": [...]}`, each entry shaped like
+/// [`single`]'s `data` object.
+pub fn collection(resource_type: &str, items: Vec<(String, Value)>) -> Value {
+    let data: Vec<Value> = items
+        .into_iter()
+        .map(|(id, attributes)| {
+            json!({
+                "type": resource_type,
+                "id": id,
+                "attributes": attributes,
+            })
+        })
+        .collect();
+    json!({ "data": data })
+}
+
+/// Renders a JSON:API body with the `application/vnd.api+json` content
+/// type, in place of axum's default `Json` extractor.
+pub fn response(body: Value) -> Response {
+    (
+        [(CONTENT_TYPE, CONTENT_TYPE_JSON_API)],
+        serde_json::to_string(&body).unwrap_or_default(),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_article_envelope() {
+        let body = single(
+            "article",
+            "how-to-train-your-dragon",
+            json!({"title": "How to train your dragon"}),
+        );
+        assert_eq!(body["data"]["type"], "article");
+        assert_eq!(body["data"]["id"], "how-to-train-your-dragon");
+        assert_eq!(body["data"]["attributes"]["title"], "How to train your dragon");
+    }
+}
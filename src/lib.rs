@@ -0,0 +1,39 @@
+//! `learn-language` is a trait-based server library implementing the
+//! Conduit ("RealWorld") API on top of axum.
+//!
+//! Consumers implement the resource traits in [`apis`] on their own
+//! application state, then call [`router::build_router`] to get a ready
+//! `axum::Router`. This crate owns routing, request/response
+//! serialization, and the wire-format [`models`]; implementors own
+//! persistence and business logic.
+
+// Response enum variants are named after their HTTP status
+// (`Status200_OK`, `Status422_UnprocessableEntity`, ...) to read as a
+// pairing of code and reason phrase rather than a prose description.
+// That's not valid UpperCamelCase by rustc's lights, but renaming every
+// variant to `Status200Ok` loses the pairing for a cosmetic win.
+#![allow(non_camel_case_types)]
+// Every `*Response` enum in `apis` carries its success payload by value
+// rather than behind a `Box`, so implementors can pattern-match and move
+// out of it without an extra indirection. `Article` is the biggest of
+// those payloads, which makes a few of these enums "large" by clippy's
+// measure; boxing them would ripple a breaking signature change through
+// every implementor for a lint that doesn't reflect an actual hot path.
+#![allow(clippy::large_enum_variant, clippy::result_large_err)]
+
+pub mod apis;
+pub mod case;
+pub mod circuit_breaker;
+pub mod concurrency;
+pub mod context;
+pub mod docs;
+pub mod introspection;
+pub mod jsonapi;
+pub mod middleware;
+pub mod models;
+pub mod router;
+#[cfg(feature = "test-util")]
+pub mod testing;
+
+pub use context::{Claims, ServerConfig};
+pub use router::build_router;
@@ -0,0 +1,656 @@
+//! Cross-cutting axum middleware layered over the router by
+//! [`crate::router::build_router`], independent of any single trait.
+
+use axum::body::Body;
+use axum::extract::{MatchedPath, Request};
+use axum::http::{HeaderName, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::time::Instant;
+
+/// Header carrying the correlation id, both incoming (reused if present)
+/// and outgoing (always set).
+pub const CORRELATION_ID_HEADER: &str = "x-request-id";
+
+/// Ensures every request has a correlation id — reusing an incoming
+/// `X-Request-Id` header, or minting a fresh UUID v4 if none was sent —
+/// and makes it available two ways: as a field (`correlation_id`) on the
+/// current [`tracing::Span`], so any trait method can read it with
+/// `tracing::Span::current()` without a signature change, and echoed back
+/// on the response so callers can correlate their logs with ours.
+///
+/// With the `otel` feature enabled, the span also carries an `otel.kind =
+/// "server"` field, the convention [`tracing-opentelemetry`](https://docs.rs/tracing-opentelemetry)
+/// reads to tag the exported span's `SpanKind`. This crate only names the
+/// field — wiring an actual `tracing_opentelemetry::layer()` (and an
+/// exporter) onto the implementor's `tracing_subscriber::Registry` is still
+/// the application's job, same as plain `tracing` always was.
+pub async fn correlation_id(request: Request, next: Next) -> Response {
+    let incoming = request
+        .headers()
+        .get(CORRELATION_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let correlation_id = incoming.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    use tracing::Instrument;
+    #[cfg(feature = "otel")]
+    let span = tracing::info_span!(
+        "request",
+        correlation_id = %correlation_id,
+        otel.kind = "server"
+    );
+    #[cfg(not(feature = "otel"))]
+    let span = tracing::info_span!("request", correlation_id = %correlation_id);
+
+    let mut response = next.run(request).instrument(span).await;
+    if let Ok(value) = HeaderValue::from_str(&correlation_id) {
+        response
+            .headers_mut()
+            .insert(HeaderName::from_static(CORRELATION_ID_HEADER), value);
+    }
+    response
+}
+
+/// Adds an `X-API-Version` header to every response, set to `version`
+/// (either this crate's `Cargo.toml` version, via
+/// [`crate::context::ServerConfig::api_version`]'s default, or an
+/// implementor-supplied override for its own build/release identifier).
+pub async fn api_version_header(version: String, request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+    if let Ok(value) = HeaderValue::from_str(&version) {
+        response
+            .headers_mut()
+            .insert(HeaderName::from_static("x-api-version"), value);
+    }
+    response
+}
+
+/// Returns the deepest nesting level in `value`, where a bare scalar is
+/// depth `1`.
+fn json_depth(value: &serde_json::Value) -> usize {
+    match value {
+        serde_json::Value::Array(items) => {
+            1 + items.iter().map(json_depth).max().unwrap_or(0)
+        }
+        serde_json::Value::Object(fields) => {
+            1 + fields.values().map(json_depth).max().unwrap_or(0)
+        }
+        _ => 1,
+    }
+}
+
+/// Returns the 0-based byte offset into `bytes` for `serde_json`'s
+/// 1-based `(line, column)` position, by summing the length of every
+/// line before it (including its trailing newline) plus `column - 1`.
+/// `serde_json`'s column is a count of UTF-8 bytes within the line for
+/// invalid-syntax errors, so this lines up directly with `bytes`.
+fn line_column_to_byte_offset(bytes: &[u8], line: usize, column: usize) -> usize {
+    let mut offset = 0;
+    for current_line in bytes.split_inclusive(|&b| b == b'\n').take(line.saturating_sub(1)) {
+        offset += current_line.len();
+    }
+    offset + column.saturating_sub(1)
+}
+
+/// Validates that every non-empty request body is well-formed JSON
+/// before it reaches a `Json<T>` extractor, returning a
+/// [`crate::models::GenericErrorModel`] that names the byte offset of the
+/// syntax error instead of axum's default rejection body. Gated by
+/// [`crate::context::ServerConfig::detailed_json_errors`], since it costs
+/// an extra parse pass over every body.
+///
+/// This only checks JSON *syntax* — a body that parses but doesn't match
+/// a given route's `T` still gets axum's own deserialization-mismatch
+/// rejection from the `Json<T>` extractor downstream.
+pub async fn detailed_json_errors(request: Request, next: Next) -> Response {
+    let (parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+    if !bytes.is_empty() {
+        if let Err(err) = serde_json::from_slice::<serde_json::Value>(&bytes) {
+            let offset = line_column_to_byte_offset(&bytes, err.line(), err.column());
+            return (
+                StatusCode::BAD_REQUEST,
+                axum::response::Json(crate::models::GenericErrorModel::new(vec![format!(
+                    "invalid JSON at byte offset {offset} (line {}, column {}): {err}",
+                    err.line(),
+                    err.column()
+                )])),
+            )
+                .into_response();
+        }
+    }
+    let request = Request::from_parts(parts, Body::from(bytes));
+    next.run(request).await
+}
+
+/// Rejects JSON request bodies nested deeper than `max_depth` with `400
+/// Bad Request`, before the body reaches any `Json<T>` extractor. Guards
+/// against deeply-nested payloads built to blow the stack of a naive
+/// recursive-descent deserializer.
+pub async fn max_json_depth(max_depth: usize, request: Request, next: Next) -> Response {
+    let (parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+    if !bytes.is_empty() {
+        match serde_json::from_slice::<serde_json::Value>(&bytes) {
+            Ok(value) if json_depth(&value) > max_depth => {
+                return StatusCode::BAD_REQUEST.into_response();
+            }
+            _ => {}
+        }
+    }
+    let request = Request::from_parts(parts, Body::from(bytes));
+    next.run(request).await
+}
+
+/// Headers that are either hop-by-hop (meaningless or actively wrong to
+/// forward from an application response) or tend to leak implementation
+/// details (server/runtime identity). Stripped by [`strip_response_headers`].
+const STRIPPED_RESPONSE_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "server",
+    "x-powered-by",
+];
+
+/// Removes [`STRIPPED_RESPONSE_HEADERS`] from every response. Axum/hyper
+/// already manage `Transfer-Encoding` and friends correctly on the happy
+/// path; this exists to catch headers an implementor's trait method set by
+/// hand (e.g. by constructing a response with extra headers attached).
+pub async fn strip_response_headers(request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    for name in STRIPPED_RESPONSE_HEADERS {
+        headers.remove(HeaderName::from_static(name));
+    }
+    response
+}
+
+/// Buffers the request body, rejecting it with `413 Payload Too Large` if
+/// it exceeds `default_limit` — unless the request's matched route is
+/// `/api/articles` or `/api/articles/:slug`, in which case `article_limit`
+/// (falling back to `default_limit` if unset) applies instead.
+///
+/// Buffering happens incrementally via [`axum::body::to_bytes`]'s own
+/// streaming read loop, which stops as soon as the running total crosses
+/// the limit rather than collecting the whole (possibly chunked-encoded)
+/// body first — so an oversized request is rejected without ever holding
+/// more than `limit` bytes in memory.
+pub async fn request_body_limit(
+    default_limit: usize,
+    article_limit: Option<usize>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let is_article_route = matches!(
+        request
+            .extensions()
+            .get::<MatchedPath>()
+            .map(|path| path.as_str()),
+        Some("/api/articles") | Some("/api/articles/:slug")
+    );
+    let limit = if is_article_route {
+        article_limit.unwrap_or(default_limit)
+    } else {
+        default_limit
+    };
+    let (parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, limit).await {
+        Ok(bytes) => bytes,
+        Err(_) => return StatusCode::PAYLOAD_TOO_LARGE.into_response(),
+    };
+    let request = Request::from_parts(parts, Body::from(bytes));
+    next.run(request).await
+}
+
+/// Logs a `tracing::warn!` if the request takes longer than `threshold`
+/// to complete, naming the matched route and the elapsed time. See
+/// [`crate::context::ServerConfig::max_request_duration_warning`].
+pub async fn max_request_duration_warning(
+    threshold: std::time::Duration,
+    request: Request,
+    next: Next,
+) -> Response {
+    let matched = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let started = Instant::now();
+    let response = next.run(request).await;
+    let elapsed = started.elapsed();
+    if elapsed > threshold {
+        tracing::warn!(route = %matched, elapsed_ms = elapsed.as_millis() as u64, "request exceeded max_request_duration_warning");
+    }
+    response
+}
+
+/// Rejects requests with an `Expect` header value other than
+/// `100-continue` with `417 Expectation Failed`.
+///
+/// Hyper already handles the `100-continue` handshake itself (emitting the
+/// interim `100 Continue` response before this crate ever sees the
+/// request), so there's nothing to do for the common case. This only
+/// exists to give a clear, consistent answer to clients that send some
+/// other `Expect` value this server can't satisfy, instead of whatever
+/// each endpoint's handler would otherwise do with it.
+pub async fn expect_continue(request: Request, next: Next) -> Response {
+    let expect = request
+        .headers()
+        .get(axum::http::header::EXPECT)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_ascii_lowercase);
+    match expect {
+        Some(value) if value != "100-continue" => {
+            StatusCode::EXPECTATION_FAILED.into_response()
+        }
+        _ => next.run(request).await,
+    }
+}
+
+/// Rejects requests whose matched route is in `disabled_routes` with `503
+/// Service Unavailable`, without calling the handler at all. Lets an
+/// operator take a single endpoint out of service via configuration.
+pub async fn disabled_routes(
+    disabled_routes: Vec<String>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let matched = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+    if matched.is_some_and(|matched| disabled_routes.iter().any(|route| route == &matched)) {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    }
+    next.run(request).await
+}
+
+/// Adds a `Deprecation: true` response header when the request's matched
+/// route pattern is in `deprecated_routes`. Routes that don't match axum's
+/// `MatchedPath` (i.e. no route matched at all, so a `404` is already on
+/// its way) are left untouched.
+pub async fn deprecation_header(
+    deprecated_routes: Vec<String>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let matched = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+    let mut response = next.run(request).await;
+    if let Some(matched) = matched {
+        if deprecated_routes.iter().any(|route| route == &matched) {
+            response.headers_mut().insert(
+                HeaderName::from_static("deprecation"),
+                HeaderValue::from_static("true"),
+            );
+        }
+    }
+    response
+}
+
+/// Adds a `Cache-Control` response header for the request's matched route,
+/// per [`crate::context::ServerConfig::cache_control_routes`]. A request
+/// carrying an `Authorization` header or `token` cookie gets
+/// `Cache-Control: private` instead, regardless of `cache_control_routes`,
+/// since this middleware runs ahead of any trait method validating the
+/// credential and so can't tell a genuine caller from a stale or forged
+/// one — only that a shared cache shouldn't reuse the response either way.
+pub async fn cache_control(
+    cache_control_routes: Vec<(String, String)>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let matched = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+    let has_credential = request.headers().get(axum::http::header::AUTHORIZATION).is_some()
+        || request
+            .headers()
+            .get(axum::http::header::COOKIE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|cookies| {
+                cookies
+                    .split(';')
+                    .filter_map(|pair| pair.split_once('='))
+                    .any(|(name, _)| name.trim() == "token")
+            });
+    let mut response = next.run(request).await;
+    let directive = if has_credential {
+        Some("private".to_string())
+    } else {
+        matched.and_then(|matched| {
+            cache_control_routes
+                .iter()
+                .find(|(route, _)| route == &matched)
+                .map(|(_, directive)| directive.clone())
+        })
+    };
+    if let Some(directive) = directive {
+        if let Ok(value) = HeaderValue::from_str(&directive) {
+            response
+                .headers_mut()
+                .insert(axum::http::header::CACHE_CONTROL, value);
+        }
+    }
+    response
+}
+
+/// Rejects requests whose `Host` header isn't in `allowed_hosts` with `400
+/// Bad Request`. `allowed_hosts` is checked exactly, without port
+/// stripping, so callers should include the port if one is expected.
+pub async fn allowed_hosts(
+    allowed_hosts: Vec<String>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let host = request
+        .headers()
+        .get(axum::http::header::HOST)
+        .and_then(|value| value.to_str().ok());
+    match host {
+        Some(host) if allowed_hosts.iter().any(|allowed| allowed == host) => {
+            next.run(request).await
+        }
+        _ => StatusCode::BAD_REQUEST.into_response(),
+    }
+}
+
+/// Returns `true` if `forwarded_proto` (the raw `X-Forwarded-Proto` header
+/// value, if present) names a scheme other than `https`. A missing header
+/// isn't itself evidence of plaintext, so this only flags a request that
+/// explicitly says it arrived some other way.
+fn is_insecure_forwarded_request(forwarded_proto: Option<&str>) -> bool {
+    matches!(forwarded_proto, Some(proto) if !proto.eq_ignore_ascii_case("https"))
+}
+
+/// Rejects a request with `403 Forbidden` if its `X-Forwarded-Proto` header
+/// names anything other than `https`, for [`crate::context::ServerConfig::require_https`].
+/// Every response that reaches this point, rejected or not, gets a
+/// `Strict-Transport-Security` header, telling the caller's browser to
+/// upgrade future requests on its own.
+pub async fn require_https(request: Request, next: Next) -> Response {
+    let forwarded_proto = request
+        .headers()
+        .get("x-forwarded-proto")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let mut response = if is_insecure_forwarded_request(forwarded_proto.as_deref()) {
+        StatusCode::FORBIDDEN.into_response()
+    } else {
+        next.run(request).await
+    };
+    response.headers_mut().insert(
+        HeaderName::from_static("strict-transport-security"),
+        HeaderValue::from_static("max-age=63072000; includeSubDomains"),
+    );
+    response
+}
+
+/// Deterministically maps `request_id` to a fraction in `[0.0, 1.0)`: the
+/// same id always lands on the same fraction, so sampling by comparing it
+/// against a configured rate doesn't need to roll fresh randomness per
+/// request (and so can't let the same request be sometimes-logged,
+/// sometimes-not).
+fn sample_fraction(request_id: &str) -> f64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    request_id.hash(&mut hasher);
+    ((hasher.finish() >> 11) as f64) / ((1u64 << 53) as f64)
+}
+
+/// Whether a request identified by `request_id` falls within `log_sample_rate`
+/// (see [`crate::context::ServerConfig::log_sample_rate`]). `0.0` never
+/// logs, `1.0` always does.
+fn should_log_sample(request_id: &str, log_sample_rate: f64) -> bool {
+    sample_fraction(request_id) < log_sample_rate
+}
+
+/// Logs a sampled fraction of requests at `info`, with route, status, and
+/// latency, per [`crate::context::ServerConfig::log_sample_rate`]. Keys the
+/// sampling decision off an incoming `X-Request-Id` header when the caller
+/// sent one (so retries of the same logical request sample the same way);
+/// otherwise this runs before [`correlation_id`] mints one, so it falls
+/// back to its own fresh id, sampled independently of whatever
+/// [`correlation_id`] ends up echoing back.
+pub async fn log_sampler(log_sample_rate: f64, request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(CORRELATION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let matched = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+    let start = Instant::now();
+    let response = next.run(request).await;
+    if should_log_sample(&request_id, log_sample_rate) {
+        tracing::info!(
+            route = matched.as_deref().unwrap_or(""),
+            status = response.status().as_u16(),
+            elapsed_ms = start.elapsed().as_millis() as u64,
+            "sampled request"
+        );
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_insecure_forwarded_request_is_false_without_the_header() {
+        assert!(!is_insecure_forwarded_request(None));
+    }
+
+    #[test]
+    fn is_insecure_forwarded_request_is_false_for_https() {
+        assert!(!is_insecure_forwarded_request(Some("https")));
+        assert!(!is_insecure_forwarded_request(Some("HTTPS")));
+    }
+
+    #[test]
+    fn is_insecure_forwarded_request_is_true_for_http() {
+        assert!(is_insecure_forwarded_request(Some("http")));
+    }
+
+    #[test]
+    fn should_log_sample_logs_nothing_at_a_rate_of_zero() {
+        assert!(!should_log_sample("any-request-id", 0.0));
+        assert!(!should_log_sample("another-request-id", 0.0));
+    }
+
+    #[test]
+    fn should_log_sample_logs_everything_at_a_rate_of_one() {
+        assert!(should_log_sample("any-request-id", 1.0));
+        assert!(should_log_sample("another-request-id", 1.0));
+    }
+
+    /// `request_body_limit` is also this crate's decompression-bomb guard
+    /// (see [`crate::context::ServerConfig::max_request_body_bytes`]):
+    /// since this crate never transparently decompresses request bodies,
+    /// a highly-compressible oversized payload is just an oversized
+    /// payload here, aborted by `axum::body::to_bytes`'s own limit before
+    /// it's ever fully buffered.
+    #[tokio::test]
+    async fn request_body_limit_rejects_a_body_over_the_limit() {
+        let app = axum::Router::new()
+            .route("/", axum::routing::post(StatusCode::OK))
+            .layer(axum::middleware::from_fn(
+                move |req: Request, next: Next| request_body_limit(8, None, req, next),
+            ));
+        let request = Request::builder()
+            .method("POST")
+            .uri("/")
+            .body(Body::from(vec![b'a'; 1024]))
+            .unwrap();
+        let response = tower::ServiceExt::oneshot(app, request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn request_body_limit_passes_a_body_within_the_limit() {
+        let app = axum::Router::new()
+            .route("/", axum::routing::post(StatusCode::OK))
+            .layer(axum::middleware::from_fn(
+                move |req: Request, next: Next| request_body_limit(8, None, req, next),
+            ));
+        let request = Request::builder()
+            .method("POST")
+            .uri("/")
+            .body(Body::from(vec![b'a'; 4]))
+            .unwrap();
+        let response = tower::ServiceExt::oneshot(app, request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn allowed_hosts_rejects_an_off_allowlist_host() {
+        let app = axum::Router::new()
+            .route("/", axum::routing::get(StatusCode::OK))
+            .layer(axum::middleware::from_fn(move |req: Request, next: Next| {
+                allowed_hosts(vec!["example.com".to_string()], req, next)
+            }));
+        let request = Request::builder()
+            .uri("/")
+            .header(axum::http::header::HOST, "evil.example")
+            .body(Body::empty())
+            .unwrap();
+        let response = tower::ServiceExt::oneshot(app, request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn allowed_hosts_passes_an_allowed_host() {
+        let app = axum::Router::new()
+            .route("/", axum::routing::get(StatusCode::OK))
+            .layer(axum::middleware::from_fn(move |req: Request, next: Next| {
+                allowed_hosts(vec!["example.com".to_string()], req, next)
+            }));
+        let request = Request::builder()
+            .uri("/")
+            .header(axum::http::header::HOST, "example.com")
+            .body(Body::empty())
+            .unwrap();
+        let response = tower::ServiceExt::oneshot(app, request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    /// Regression test for registration order: `detailed_json_errors`
+    /// buffers the whole body to parse it, so it has to run *inside*
+    /// `request_body_limit`'s cap (registered here the same way
+    /// [`crate::router::build_router`] registers them — `detailed_json_errors`
+    /// as a plain `.layer()`, `request_body_limit` afterward as a
+    /// `.route_layer()` — so `request_body_limit` becomes the
+    /// most-recently-registered, outermost, first-to-run layer). An
+    /// oversized body must come back `413`, not the `400` `detailed_json_errors`
+    /// would produce if it ran first and tried to parse a half-buffered body.
+    #[tokio::test]
+    async fn detailed_json_errors_does_not_run_before_the_body_size_cap() {
+        let app = axum::Router::new()
+            .route("/", axum::routing::post(StatusCode::OK))
+            .layer(axum::middleware::from_fn(detailed_json_errors))
+            .route_layer(axum::middleware::from_fn(
+                move |req: Request, next: Next| request_body_limit(8, None, req, next),
+            ));
+        let request = Request::builder()
+            .method("POST")
+            .uri("/")
+            .body(Body::from(vec![b'a'; 1024]))
+            .unwrap();
+        let response = tower::ServiceExt::oneshot(app, request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    /// Minimal [`tracing::Subscriber`] that records each event's message,
+    /// just enough to assert `max_request_duration_warning`'s `warn!` fired
+    /// without pulling in `tracing-subscriber` as a dependency.
+    struct RecordingSubscriber {
+        messages: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    struct MessageVisitor(String);
+
+    impl tracing::field::Visit for MessageVisitor {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "message" {
+                self.0 = format!("{value:?}");
+            }
+        }
+    }
+
+    impl tracing::Subscriber for RecordingSubscriber {
+        fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            tracing::span::Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+        fn event(&self, event: &tracing::Event<'_>) {
+            let mut visitor = MessageVisitor(String::new());
+            event.record(&mut visitor);
+            self.messages.lock().unwrap().push(visitor.0);
+        }
+
+        fn enter(&self, _span: &tracing::span::Id) {}
+
+        fn exit(&self, _span: &tracing::span::Id) {}
+    }
+
+    /// `max_request_duration_warning` can only warn *after* a slow handler
+    /// returns — this crate has no mechanism to detect a handler that's
+    /// still hung, only one that has already finished too slowly — so a
+    /// handler that sleeps past the threshold is the closest honest stand-in
+    /// for "a hung mock" this middleware can observe.
+    #[tokio::test]
+    async fn max_request_duration_warning_logs_once_the_threshold_is_exceeded() {
+        let messages = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let subscriber = RecordingSubscriber {
+            messages: messages.clone(),
+        };
+        let app = axum::Router::new()
+            .route(
+                "/",
+                axum::routing::get(|| async {
+                    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                    StatusCode::OK
+                }),
+            )
+            .layer(axum::middleware::from_fn(move |req: Request, next: Next| {
+                max_request_duration_warning(std::time::Duration::from_millis(1), req, next)
+            }));
+        let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+        let _guard = tracing::subscriber::set_default(subscriber);
+        let response = tower::ServiceExt::oneshot(app, request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(messages
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|message| message.contains("exceeded max_request_duration_warning")));
+    }
+}
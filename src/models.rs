@@ -0,0 +1,641 @@
+//! Wire-format data models shared across the `apis` traits.
+//!
+//! These mirror the JSON shapes defined by the Conduit ("RealWorld") API
+//! spec: every resource is nested under a singular or plural key matching
+//! its type (e.g. `{"article": {...}}`, `{"articles": [...]}`).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Whether a personalization flag (`favorited`, `following`) is known for
+/// the current caller.
+///
+/// Anonymous requests and authenticated requests both serialize this as a
+/// plain JSON boolean, so the wire format is unchanged; the distinction
+/// exists so implementors can't accidentally write `false` when the real
+/// answer is "not applicable, there's no caller to personalize for".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PersonalizationFlag {
+    /// No authenticated caller; serializes as `false`.
+    NotApplicable,
+    /// An authenticated caller, and whether they favorited/followed.
+    Known(bool),
+}
+
+impl PersonalizationFlag {
+    pub fn as_bool(self) -> bool {
+        matches!(self, PersonalizationFlag::Known(true))
+    }
+}
+
+impl From<bool> for PersonalizationFlag {
+    fn from(known: bool) -> Self {
+        PersonalizationFlag::Known(known)
+    }
+}
+
+impl Serialize for PersonalizationFlag {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bool(self.as_bool())
+    }
+}
+
+impl<'de> Deserialize<'de> for PersonalizationFlag {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        bool::deserialize(deserializer).map(PersonalizationFlag::Known)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub username: String,
+    pub bio: Option<String>,
+    pub image: Option<String>,
+    pub following: PersonalizationFlag,
+}
+
+/// An opaque bearer token, as returned in `User::token` and
+/// `User::refresh_token`.
+///
+/// This crate never inspects a token's contents — it's whatever string the
+/// implementor's auth layer issued — but wrapping it distinguishes "a
+/// token" from "some other string" at the type level, so e.g. accidentally
+/// passing `username` where a token is expected is a compile error instead
+/// of a silent mix-up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AuthToken(pub String);
+
+impl AuthToken {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for AuthToken {
+    fn from(token: String) -> Self {
+        AuthToken(token)
+    }
+}
+
+impl std::fmt::Display for AuthToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub email: String,
+    pub token: AuthToken,
+    /// A longer-lived token for exchanging an expired session token
+    /// without re-entering credentials. Only ever populated by `login`
+    /// and `rotate_token`; every other endpoint returning a `User`
+    /// leaves it `None`, and it's omitted from the response body then.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub refresh_token: Option<AuthToken>,
+    pub username: String,
+    pub bio: Option<String>,
+    pub image: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserResponse {
+    pub user: User,
+}
+
+/// The public subset of [`User`], with `email` and `token` left out, for
+/// `GET /api/users/:username`. Unlike [`Profile`], this carries no
+/// `following` flag — it's not a social profile, just the non-private
+/// fields of the account.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PublicUser {
+    pub username: String,
+    pub bio: Option<String>,
+    pub image: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicUserResponse {
+    pub user: PublicUser,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewUser {
+    pub username: String,
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewUserRequest {
+    pub user: NewUser,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LoginUser {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LoginUserRequest {
+    pub user: LoginUser,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UpdateUser {
+    pub email: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub bio: Option<String>,
+    pub image: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateUserRequest {
+    pub user: UpdateUser,
+}
+
+/// Optional body for `DELETE /api/user`. Unlike every other request body in
+/// this crate, it isn't nested under a resource key, since there's no
+/// plural/singular resource name to nest it under — just a re-authentication
+/// check on the caller's own account.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DeleteAccountRequest {
+    /// The caller's current password, for implementors that want a fresh
+    /// credential check before an irreversible delete rather than relying
+    /// on the bearer token alone. `None` skips the re-check.
+    pub password: Option<String>,
+}
+
+/// A single instant, suitable for both `created_at` and `updated_at` on a
+/// newly-created resource.
+///
+/// Calling `Utc::now()` twice in a row to fill in both fields can return
+/// two different instants if the clock ticks between the calls, so
+/// `created_at` and `updated_at` end up a few microseconds apart on a row
+/// that's never been updated. Call this once instead and use the result
+/// for both.
+pub fn fresh_timestamp() -> DateTime<Utc> {
+    Utc::now()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Article {
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub body: String,
+    pub tag_list: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub favorited: PersonalizationFlag,
+    pub favorites_count: i64,
+    pub author: Profile,
+    /// Whether the caller may edit/delete this article, as resolved by
+    /// [`crate::apis::articles::Articles::can_edit`]. `None` when the
+    /// router has no occasion to compute it (e.g. list endpoints, or no
+    /// authenticated caller) rather than `Some(false)` — omitted from the
+    /// serialized response in that case, so existing clients that predate
+    /// this field see no change in shape.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub can_edit: Option<bool>,
+}
+
+/// Response body for `GET /api/profiles/:username/summary`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileSummary {
+    pub profile: Profile,
+    pub articles: Vec<Article>,
+}
+
+/// Response body for `GET /api/articles/:slug/oembed`, per the
+/// [oEmbed](https://oembed.com) spec's `"link"` type — the minimal shape
+/// that doesn't require embeddable HTML/dimensions.
+#[derive(Debug, Clone, Serialize)]
+pub struct OEmbed {
+    #[serde(rename = "type")]
+    pub type_: &'static str,
+    pub version: &'static str,
+    pub title: String,
+    pub author_name: String,
+    pub author_url: String,
+    pub provider_name: &'static str,
+}
+
+/// The `author` projection inside [`AmpArticle`].
+#[derive(Debug, Clone, Serialize)]
+pub struct AmpAuthor {
+    pub username: String,
+    pub image: Option<String>,
+}
+
+/// A minimal, renderable-only projection of [`Article`], for `GET
+/// /api/articles/:slug?profile=amp`. Unlike the generic `fields`
+/// projection (which lets a client pick whatever subset it wants), this
+/// is a named, stable shape a client can code against without
+/// revalidating it every time this crate adds an `Article` field.
+/// Field names are fixed camelCase regardless of
+/// [`crate::context::ServerConfig::json_case`], since the whole point is
+/// a contract that doesn't move.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmpArticle {
+    pub slug: String,
+    pub title: String,
+    pub body: String,
+    pub author: AmpAuthor,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Article> for AmpArticle {
+    fn from(article: Article) -> Self {
+        Self {
+            slug: article.slug,
+            title: article.title,
+            body: article.body,
+            author: AmpAuthor {
+                username: article.author.username,
+                image: article.author.image,
+            },
+            created_at: article.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SingleArticle {
+    pub article: Article,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MultipleArticles {
+    pub articles: Vec<Article>,
+    pub articles_count: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<PaginationLinks>,
+}
+
+/// HATEOAS-style pagination links, built from the request's `limit`/
+/// `offset` and the total result count. Omitted entirely unless the
+/// caller opted in (see `ServerConfig::pagination_links`).
+#[derive(Debug, Clone, Serialize)]
+pub struct PaginationLinks {
+    #[serde(rename = "self")]
+    pub self_: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev: Option<String>,
+    pub first: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewArticle {
+    pub title: String,
+    pub description: String,
+    pub body: String,
+    pub tag_list: Option<Vec<String>>,
+    /// Whether this article is publicly listed. `None` (the default)
+    /// means published: omitting the field keeps today's behavior of
+    /// every new article being visible immediately. Set to `Some(false)`
+    /// to save it as a draft instead, visible only via
+    /// `Articles::draft_articles`.
+    pub published: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewArticleRequest {
+    pub article: NewArticle,
+}
+
+/// Request body for `POST /api/articles/bulk`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BulkImportArticlesRequest {
+    pub articles: Vec<NewArticle>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UpdateArticle {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub body: Option<String>,
+    /// Flips the article between published and draft. `None` leaves its
+    /// current state unchanged.
+    pub published: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateArticleRequest {
+    pub article: UpdateArticle,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Comment {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub body: String,
+    pub author: Profile,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SingleComment {
+    pub comment: Comment,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MultipleComments {
+    pub comments: Vec<Comment>,
+}
+
+/// One entry in `GET /api/comments/recent`'s feed: a [`Comment`] plus the
+/// slug of the article it was posted on, since that feed spans every
+/// article rather than being nested under one.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentComment {
+    pub article_slug: String,
+    pub comment: Comment,
+}
+
+/// Body for `GET /api/comments/recent`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentComments {
+    pub comments: Vec<RecentComment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewComment {
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewCommentRequest {
+    pub comment: NewComment,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TagList {
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RenameTagRequest {
+    pub tag: String,
+}
+
+/// Request body for `PUT /api/profiles/:username/follow`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SetFollowRequest {
+    pub following: bool,
+}
+
+/// A single item's outcome in a batch operation, keyed by whatever
+/// identifier the request used to ask for it (a slug, a username, ...).
+///
+/// Used by [`crate::apis::comments::Comments::delete_comments`]. The other
+/// batch endpoints serialize their results in their own shapes: `POST
+/// /api/articles/bulk` as [`crate::apis::articles::BulkImportResult`] and
+/// `POST /api/batch` as [`BatchSubResponse`].
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchResult<T> {
+    pub key: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<GenericErrorModel>,
+}
+
+impl<T> BatchResult<T> {
+    pub fn ok(key: impl Into<String>, data: T) -> Self {
+        Self {
+            key: key.into(),
+            ok: true,
+            data: Some(data),
+            error: None,
+        }
+    }
+
+    pub fn err(key: impl Into<String>, error: GenericErrorModel) -> Self {
+        Self {
+            key: key.into(),
+            ok: false,
+            data: None,
+            error: Some(error),
+        }
+    }
+}
+
+impl<T> std::fmt::Display for BatchResult<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ok {
+            write!(f, "{}: ok", self.key)
+        } else {
+            write!(f, "{}: failed", self.key)
+        }
+    }
+}
+
+/// Body for `GET /api/stats`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    pub articles: i64,
+    pub users: i64,
+    pub tags: i64,
+    pub comments: i64,
+}
+
+/// Body returned alongside `202 Accepted` for an operation an
+/// implementor chooses to process in the background (e.g. an export or
+/// a deletion) rather than synchronously. `status_url` is also echoed
+/// as the response's `Location` header, so clients that only look at
+/// headers and clients that only look at the body both find it.
+#[derive(Debug, Clone, Serialize)]
+pub struct AsyncOperationAccepted {
+    pub status_url: String,
+}
+
+/// Request body for `POST /api/batch`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchRequest {
+    pub requests: Vec<BatchSubRequest>,
+}
+
+/// One request within a [`BatchRequest`]. Only `GET` is accepted — a
+/// batch is for fetching several resources in one round trip, not for
+/// smuggling writes past whatever guards a single request would hit.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchSubRequest {
+    pub method: String,
+    pub path: String,
+}
+
+/// One entry in a `POST /api/batch` response, in the same order as the
+/// corresponding [`BatchSubRequest`].
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchSubResponse {
+    pub status: u16,
+    pub body: serde_json::Value,
+}
+
+/// One entry in a `GET /api/articles/changes` response, tagged by the
+/// kind of modification it represents. `id` is the feed's own monotonic
+/// id, not the article's identity — implementors typically assign a
+/// fresh one on every create, update, and delete, so the same slug can
+/// appear under several ids over time.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChangeEntry {
+    Created { id: i64, article: Article },
+    Updated { id: i64, article: Article },
+    /// A tombstone: `slug` existed as of some earlier id but has since
+    /// been deleted. Included so sync clients can evict it from a local
+    /// cache instead of only ever learning about creates and updates.
+    Deleted { id: i64, slug: String },
+}
+
+/// Body for `GET /api/articles/changes`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ArticleChanges {
+    pub changes: Vec<ChangeEntry>,
+    /// The `since` value to pass on the next request to continue the
+    /// feed from where this page left off. `None` means the caller has
+    /// caught up to the end of the feed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_since: Option<i64>,
+}
+
+/// Body for `GET /api/user/export`: everything this crate knows about one
+/// account, for data-portability requests. `favorited_articles` is the
+/// caller's favorites list, distinct from `articles`, which is what the
+/// caller has authored.
+#[derive(Debug, Clone, Serialize)]
+pub struct UserDataExport {
+    pub profile: User,
+    pub articles: Vec<Article>,
+    pub comments: Vec<Comment>,
+    pub favorited_articles: Vec<Article>,
+}
+
+/// Error payload shape used by every `422` response across the API, and by
+/// the `400`/`500` responses the router builds itself: `{"errors": {"body":
+/// ["is required", ...]}}`. `401` and `403` responses carry no body. A small number of
+/// responses (e.g. a registration conflict) key their messages by field
+/// name instead of `body` — see [`GenericErrorModel::field`].
+#[derive(Debug, Clone, Serialize)]
+pub struct GenericErrorModel {
+    pub errors: GenericErrorModelErrors,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GenericErrorModelErrors {
+    #[serde(flatten)]
+    pub fields: std::collections::BTreeMap<String, Vec<String>>,
+}
+
+impl GenericErrorModel {
+    /// Builds the usual `{"errors": {"body": [...]}}` shape, for validation
+    /// failures that aren't attributable to one specific field.
+    pub fn new(messages: impl IntoIterator<Item = String>) -> Self {
+        Self::field("body", messages)
+    }
+
+    /// Builds `{"errors": {<field>: [...]}}`, for failures attributable to
+    /// one specific field — e.g. `GenericErrorModel::field("username",
+    /// ["has already been taken".to_string()])` for a registration
+    /// conflict.
+    pub fn field(field: impl Into<String>, messages: impl IntoIterator<Item = String>) -> Self {
+        let mut fields = std::collections::BTreeMap::new();
+        fields.insert(field.into(), messages.into_iter().collect());
+        Self {
+            errors: GenericErrorModelErrors { fields },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_profile() -> Profile {
+        Profile {
+            username: "evan".to_string(),
+            bio: None,
+            image: None,
+            following: PersonalizationFlag::NotApplicable,
+        }
+    }
+
+    fn test_article() -> Article {
+        Article {
+            slug: "how-to-train-your-dragon".to_string(),
+            title: "How to train your dragon".to_string(),
+            description: String::new(),
+            body: String::new(),
+            tag_list: Vec::new(),
+            created_at: fresh_timestamp(),
+            updated_at: fresh_timestamp(),
+            favorited: PersonalizationFlag::NotApplicable,
+            favorites_count: 0,
+            author: test_profile(),
+            can_edit: None,
+        }
+    }
+
+    #[test]
+    fn user_data_export_has_one_section_per_data_category() {
+        let export = UserDataExport {
+            profile: User {
+                email: "evan@example.com".to_string(),
+                token: AuthToken("token".to_string()),
+                refresh_token: None,
+                username: "evan".to_string(),
+                bio: None,
+                image: None,
+            },
+            articles: vec![test_article()],
+            comments: vec![Comment {
+                id: 1,
+                created_at: fresh_timestamp(),
+                updated_at: fresh_timestamp(),
+                body: "nice dragon".to_string(),
+                author: test_profile(),
+            }],
+            favorited_articles: vec![test_article()],
+        };
+        let body = serde_json::to_value(&export).unwrap();
+        for section in ["profile", "articles", "comments", "favorited_articles"] {
+            assert!(body.get(section).is_some(), "missing {section} section");
+        }
+        assert_eq!(body["articles"].as_array().unwrap().len(), 1);
+        assert_eq!(body["comments"].as_array().unwrap().len(), 1);
+        assert_eq!(body["favorited_articles"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn generic_error_model_new_keys_messages_under_body() {
+        let model = GenericErrorModel::new(vec!["is required".to_string()]);
+        let body = serde_json::to_value(&model).unwrap();
+        assert_eq!(body, serde_json::json!({"errors": {"body": ["is required"]}}));
+    }
+
+    #[test]
+    fn generic_error_model_field_keys_messages_under_the_named_field() {
+        let model = GenericErrorModel::field("username", vec!["has already been taken".to_string()]);
+        let body = serde_json::to_value(&model).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"errors": {"username": ["has already been taken"]}})
+        );
+    }
+}
@@ -0,0 +1,2571 @@
+//! Wires the `apis` traits onto concrete axum routes.
+//!
+//! Every handler is a thin adapter: extract axum inputs, call the trait
+//! method, translate the returned `*Response` enum into an HTTP response.
+//! The translation lives next to each handler rather than via a blanket
+//! `IntoResponse` impl, since every operation's status/body pairing is
+//! slightly different.
+//!
+//! Every `match` over a `*Response` enum in this file is written without a
+//! wildcard `_` arm on purpose: that's what makes adding a variant to, say,
+//! [`crate::apis::articles::GetArticleResponse`] a compile error here until
+//! the new variant is handled, rather than a response silently falling
+//! through to whatever the wildcard arm did. Don't add a catch-all arm to
+//! these matches even if it looks redundant.
+
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Json, Response};
+use axum::routing::{delete, get, post, put};
+use axum::Router;
+
+use crate::apis::articles::{Articles, ListArticlesParams};
+use crate::apis::auth::ClaimsResolver;
+use crate::apis::authorization::{Authorization, Operation, ResourceId};
+use crate::apis::comments::Comments;
+use crate::apis::profiles::Profiles;
+use crate::apis::stats::Stats;
+use crate::apis::tags::Tags;
+use crate::apis::users::{self, Users};
+use crate::context::{Claims, HasServerConfig};
+use crate::models::{
+    DeleteAccountRequest, NewArticleRequest, NewCommentRequest, NewUserRequest, SingleArticle,
+    SingleComment, TagList, UpdateArticleRequest, UpdateUserRequest, UserResponse,
+};
+
+/// Trait bound shared by every route in [`build_router`]. Implementors
+/// satisfy this by implementing each resource trait on their app state.
+pub trait AppState:
+    Articles
+    + Authorization
+    + ClaimsResolver
+    + Comments
+    + Profiles
+    + Stats
+    + crate::apis::WebhookDispatcher
+    + Tags
+    + Users
+    + HasServerConfig
+    + Clone
+    + Send
+    + Sync
+    + 'static
+{
+}
+
+impl<T> AppState for T where
+    T: Articles
+        + Authorization
+        + ClaimsResolver
+        + Comments
+        + Profiles
+        + Stats
+    + crate::apis::WebhookDispatcher
+        + Tags
+        + Users
+        + HasServerConfig
+        + Clone
+        + Send
+        + Sync
+        + 'static
+{
+}
+
+/// Extracts the value of cookie `name` from a raw `Cookie` header value
+/// (`"a=1; b=2"`), or `None` if it's absent. Doesn't unescape or validate
+/// the value; callers pass it straight on to the same resolver a header
+/// would go through, so percent-encoding quirks surface the same way in
+/// both paths.
+fn cookie_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    let header = headers.get(axum::http::header::COOKIE)?.to_str().ok()?;
+    header.split(';').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        (key.trim() == name).then(|| value.trim())
+    })
+}
+
+/// Resolves [`Claims`] from the request's `Authorization` header and/or
+/// `token` cookie via `state`'s [`ClaimsResolver`] implementation.
+///
+/// When only one credential is present (or present and valid), it's used
+/// as-is. When both are present and valid but resolve to different users,
+/// [`ConflictingAuthAction`] decides what happens: `PreferHeader` (the
+/// default) silently keeps the header's claims, `Reject` returns `Err`
+/// with a `400 Bad Request` response instead of picking one.
+///
+/// [`ConflictingAuthAction`]: crate::context::ConflictingAuthAction
+async fn claims_from_headers<S: AppState>(
+    state: &S,
+    headers: &HeaderMap,
+) -> Result<Option<Claims>, Response> {
+    let header = headers.get("authorization").and_then(|v| v.to_str().ok());
+    let header_claims = state.resolve_claims(header).await;
+    let cookie = cookie_value(headers, "token");
+    let cookie_claims = match cookie {
+        Some(cookie) => state.resolve_claims(Some(cookie)).await,
+        None => None,
+    };
+    resolve_conflicting_claims(header_claims, cookie_claims, state.server_config().conflicting_auth_action)
+}
+
+/// The merge logic behind [`claims_from_headers`], pulled out as a pure
+/// function so the conflicting-credentials cases can be tested without an
+/// `AppState`.
+fn resolve_conflicting_claims(
+    header_claims: Option<Claims>,
+    cookie_claims: Option<Claims>,
+    action: crate::context::ConflictingAuthAction,
+) -> Result<Option<Claims>, Response> {
+    match (header_claims, cookie_claims) {
+        (Some(header_claims), Some(cookie_claims)) => {
+            if header_claims.user_id == cookie_claims.user_id {
+                Ok(Some(header_claims))
+            } else {
+                match action {
+                    crate::context::ConflictingAuthAction::PreferHeader => Ok(Some(header_claims)),
+                    crate::context::ConflictingAuthAction::Reject => Err(error_response(
+                        StatusCode::BAD_REQUEST,
+                        vec!["the Authorization header and token cookie identify different users".to_string()],
+                    )),
+                }
+            }
+        }
+        (Some(claims), None) | (None, Some(claims)) => Ok(Some(claims)),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Builds a `status` response with an explicit empty body and
+/// `Content-Length: 0`, for endpoints like `delete_article` that never
+/// have anything to return. Axum's default empty body already omits
+/// `Transfer-Encoding`, but setting `Content-Length` explicitly avoids
+/// relying on that default holding across every body type we might swap
+/// in later (e.g. a streaming body).
+fn empty(status: StatusCode) -> Response {
+    (status, [(axum::http::header::CONTENT_LENGTH, "0")], ()).into_response()
+}
+
+/// Renders `value` as a JSON response body, applying `case`'s key-casing
+/// convention. Use in place of bare `Json(value).into_response()` for any
+/// Conduit-shaped (non-JSON:API) response so `ServerConfig::json_case` is
+/// honored.
+///
+/// If `max_response_bytes` is set and the serialized body exceeds it,
+/// logs a `tracing::error!` and returns `500 Internal Server Error`
+/// instead — this is meant to catch an implementor accidentally
+/// returning an unbounded list, not to politely reject an oversized
+/// request, so it fails loudly rather than truncating.
+fn render(value: impl serde::Serialize, case: crate::case::JsonCase, max_response_bytes: Option<usize>) -> Response {
+    let body = crate::case::render(value, case);
+    if let Some(max) = max_response_bytes {
+        if body.len() > max {
+            tracing::error!(
+                size = body.len(),
+                max,
+                "serialized response exceeds max_response_bytes"
+            );
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                vec!["response exceeded the configured maximum size".to_string()],
+            );
+        }
+    }
+    (
+        [(axum::http::header::CONTENT_TYPE, "application/json")],
+        body,
+    )
+        .into_response()
+}
+
+/// Checks `tags` against [`crate::context::ServerConfig::allowed_tags`],
+/// returning one message per disallowed tag, or an empty vec if
+/// `allowed_tags` is `None` (any tag allowed) or every tag is in it.
+fn check_allowed_tags(tags: &[String], allowed: &Option<std::collections::HashSet<String>>) -> Vec<String> {
+    let Some(allowed) = allowed else {
+        return Vec::new();
+    };
+    tags.iter()
+        .filter(|tag| !allowed.contains(*tag))
+        .map(|tag| format!("tag '{tag}' is not allowed"))
+        .collect()
+}
+
+/// Returns one message per tag in `tags` that also appears earlier in
+/// `tags`, for [`crate::context::ServerConfig::reject_duplicate_tags`]'s
+/// strict mode. Empty if every tag is unique.
+fn check_duplicate_tags(tags: &[String]) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    tags.iter()
+        .filter(|tag| !seen.insert(tag.as_str()))
+        .map(|tag| format!("tag '{tag}' is duplicated"))
+        .collect()
+}
+
+/// Removes duplicate tags from `tags`, keeping each one's first
+/// occurrence, for [`crate::context::ServerConfig::reject_duplicate_tags`]'s
+/// lenient (default) mode.
+fn dedupe_tags(tags: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    tags.into_iter().filter(|tag| seen.insert(tag.clone())).collect()
+}
+
+/// Returns `428 Precondition Required` if
+/// [`crate::context::ServerConfig::require_conditional_writes`] is set
+/// and `headers` carries no `If-Match`. Checked before any
+/// value-comparison against a current `If-Match`/etag, since a missing
+/// precondition and a mismatched one are different failures.
+fn require_if_match(headers: &HeaderMap, required: bool) -> Option<Response> {
+    if required && !headers.contains_key(axum::http::header::IF_MATCH) {
+        Some(StatusCode::PRECONDITION_REQUIRED.into_response())
+    } else {
+        None
+    }
+}
+
+/// Trims `params.tag`, `params.author`, and `params.favorited`, then
+/// rejects the request with `400 Bad Request` if any of them became
+/// empty in the process — e.g. `?tag=%20%20` is almost certainly a client
+/// bug, not a request to filter on the literal empty string, and passing
+/// it through as-is would silently change what `list_articles` matches.
+/// `?tag=rust` and `?tag=%20rust%20` filter identically.
+fn trim_list_articles_params(mut params: ListArticlesParams) -> Result<ListArticlesParams, Response> {
+    let mut empty = Vec::new();
+    for (name, value) in [
+        ("tag", &mut params.tag),
+        ("author", &mut params.author),
+        ("favorited", &mut params.favorited),
+    ] {
+        if let Some(trimmed) = value.as_deref().map(str::trim) {
+            if trimmed.is_empty() {
+                empty.push(format!("{name} must not be blank"));
+            } else if trimmed.len() != value.as_ref().map(String::len).unwrap_or(0) {
+                *value = Some(trimmed.to_string());
+            }
+        }
+    }
+    if !empty.is_empty() {
+        return Err(error_response(StatusCode::BAD_REQUEST, empty));
+    }
+    Ok(params)
+}
+
+/// Resolves a `Query<T>` extraction result against
+/// `ServerConfig::query_param_strict`: a malformed query string is a `400`
+/// when `strict`, or silently becomes `T::default()` when not. Handlers
+/// that want the lenient option take `Result<Query<T>, QueryRejection>`
+/// instead of a bare `Query<T>`, since axum would otherwise reject a bad
+/// query string itself before the handler — and the config check — ever
+/// runs.
+fn query_or_default<T: Default>(
+    result: Result<Query<T>, axum::extract::rejection::QueryRejection>,
+    strict: bool,
+) -> Result<T, Response> {
+    match result {
+        Ok(Query(value)) => Ok(value),
+        Err(rejection) if strict => {
+            Err(error_response(StatusCode::BAD_REQUEST, vec![rejection.body_text()]))
+        }
+        Err(_) => Ok(T::default()),
+    }
+}
+
+/// Builds a `GenericErrorModel` response with a `Content-Language: en`
+/// header. This crate's built-in error messages (validation failures,
+/// malformed query strings, etc.) are all hardcoded English — this header
+/// just says so accurately, rather than implying negotiation this crate
+/// doesn't do. Implementors with their own translated messages should
+/// build their own response instead of using this helper.
+fn error_response(status: StatusCode, errors: Vec<String>) -> Response {
+    let mut response = (
+        status,
+        Json(crate::models::GenericErrorModel::new(errors)),
+    )
+        .into_response();
+    response.headers_mut().insert(
+        axum::http::header::CONTENT_LANGUAGE,
+        HeaderValue::from_static("en"),
+    );
+    response
+}
+
+/// Adds an RFC 7234 `Warning` header to `response` if `message` is set.
+fn with_warning_header(mut response: Response, message: Option<String>) -> Response {
+    if let Some(message) = message {
+        if let Ok(value) = HeaderValue::from_str(&format!("199 learn-language \"{}\"", message)) {
+            response.headers_mut().insert(
+                HeaderName::from_static("warning"),
+                value,
+            );
+        }
+    }
+    response
+}
+
+/// Adds one RFC 7234 `Warning` header per entry in `warnings` to
+/// `response`. Unlike [`with_warning_header`], this appends rather than
+/// replaces, since a request can accumulate more than one non-fatal
+/// validation warning.
+fn with_warnings_header(mut response: Response, warnings: Vec<String>) -> Response {
+    for warning in warnings {
+        if let Ok(value) = HeaderValue::from_str(&format!("199 learn-language \"{}\"", warning)) {
+            response
+                .headers_mut()
+                .append(HeaderName::from_static("warning"), value);
+        }
+    }
+    response
+}
+
+/// Adds `X-Partial-Personalization: true` to `response` if `partial` is
+/// set, per [`crate::apis::articles::Articles::partial_personalization`]
+/// / [`crate::apis::comments::Comments::partial_personalization`].
+fn with_partial_personalization_header(mut response: Response, partial: bool) -> Response {
+    if partial {
+        response.headers_mut().insert(
+            HeaderName::from_static("x-partial-personalization"),
+            HeaderValue::from_static("true"),
+        );
+    }
+    response
+}
+
+/// Builds a `202 Accepted` response for a background operation, with
+/// `status_url` in both the `Location` header and the JSON body.
+fn accepted(status_url: String) -> Response {
+    let mut response = (
+        StatusCode::ACCEPTED,
+        Json(crate::models::AsyncOperationAccepted {
+            status_url: status_url.clone(),
+        }),
+    )
+        .into_response();
+    if let Ok(value) = HeaderValue::from_str(&status_url) {
+        response
+            .headers_mut()
+            .insert(axum::http::header::LOCATION, value);
+    }
+    response
+}
+
+/// Adds `X-RateLimit-*` headers to `response` if `state` is tracking one.
+fn with_rate_limit_headers(mut response: Response, state: Option<crate::context::RateLimitState>) -> Response {
+    if let Some(state) = state {
+        let headers = response.headers_mut();
+        headers.insert(
+            HeaderName::from_static("x-ratelimit-limit"),
+            HeaderValue::from(state.limit),
+        );
+        headers.insert(
+            HeaderName::from_static("x-ratelimit-remaining"),
+            HeaderValue::from(state.remaining),
+        );
+        headers.insert(
+            HeaderName::from_static("x-ratelimit-reset"),
+            HeaderValue::from(state.reset),
+        );
+    }
+    response
+}
+
+pub fn build_router<S: AppState>(state: S) -> Router {
+    let allowed_hosts = state.server_config().allowed_hosts.clone();
+    let strip_headers = state.server_config().strip_response_headers;
+    let max_json_depth = state.server_config().max_json_depth;
+    let swagger_ui = state.server_config().swagger_ui;
+    let root_response = state.server_config().root_response.clone();
+    let robots_txt = state.server_config().robots_txt.clone();
+    let security_txt = state.server_config().security_txt.clone();
+    let max_request_body_bytes = state.server_config().max_request_body_bytes;
+    let route_introspection = state.server_config().route_introspection;
+    let deprecated_routes = state.server_config().deprecated_routes.clone();
+    let cache_control_routes = state.server_config().cache_control_routes.clone();
+    let max_article_body_bytes = state.server_config().max_article_body_bytes;
+    let disabled_routes = state.server_config().disabled_routes.clone();
+    let max_request_duration_warning = state.server_config().max_request_duration_warning;
+    let api_version = state.server_config().api_version.clone();
+    let detailed_json_errors = state.server_config().detailed_json_errors;
+    let require_https = state.server_config().require_https;
+    let log_sample_rate = state.server_config().log_sample_rate;
+    let mut router = Router::new()
+        .route("/api/users/login", post(login::<S>))
+        .route("/api/users", post(register::<S>))
+        .route("/api/users/:username", get(get_user_by_username::<S>))
+        .route(
+            "/api/user",
+            get(current_user::<S>)
+                .put(update_user::<S>)
+                .patch(update_user::<S>)
+                .delete(delete_account::<S>),
+        )
+        .route("/api/user/token", post(rotate_token::<S>))
+        .route("/api/user/export", get(export_user_data::<S>))
+        .route("/api/user/drafts", get(draft_articles::<S>))
+        .route("/api/articles", get(list_articles::<S>).post(create_article::<S>))
+        .route("/api/articles/leaderboard", get(leaderboard::<S>))
+        .route("/api/articles/changes", get(changes_since::<S>))
+        .route("/api/articles/slugify", get(slugify_title::<S>))
+        .route("/api/articles/options", get(list_articles_options))
+        .route("/api/articles/bulk", post(bulk_import_articles::<S>))
+        .route(
+            "/api/articles/:slug",
+            get(get_article::<S>)
+                .put(update_article::<S>)
+                .delete(delete_article::<S>),
+        )
+        .route("/api/articles/:slug/export", get(export_article::<S>))
+        .route("/api/articles/:slug/oembed", get(oembed_article::<S>))
+        .route(
+            "/api/articles/:slug/favorite",
+            post(favorite_article::<S>).delete(unfavorite_article::<S>),
+        )
+        .route(
+            "/api/articles/:slug/comments/:id",
+            delete(delete_comment::<S>),
+        )
+        .route(
+            "/api/articles/:slug/comments",
+            get(get_comments::<S>)
+                .post(add_comment::<S>)
+                .delete(delete_comments::<S>),
+        )
+        .route("/api/comments/recent", get(get_recent_comments::<S>))
+        .route("/api/profiles/:username", get(get_profile::<S>))
+        .route("/api/profiles/:username/summary", get(profile_summary::<S>))
+        .route("/api/profiles/:username/tags", get(author_tags::<S>))
+        .route(
+            "/api/profiles/:username/follow",
+            post(follow_user::<S>)
+                .delete(unfollow_user::<S>)
+                .put(set_follow::<S>),
+        )
+        .route(
+            "/api/tags/:tag",
+            put(rename_tag::<S>).delete(delete_tag::<S>),
+        )
+        .route("/api/tags", get(get_tags::<S>))
+        .route("/api/tags/popular", get(popular_tags::<S>))
+        .route("/api/stats", get(get_stats::<S>))
+        .with_state(state);
+    // `/api/batch` dispatches each sub-request through the fully-built
+    // router, including every layer registered below — but those layers
+    // only ever apply to routes already present when `.layer()`/
+    // `.route_layer()` runs, so this route has to be registered before
+    // that chain for the *top-level* `POST /api/batch` request to get the
+    // same treatment. That means the router `batch` dispatches into can't
+    // be captured until after the chain finishes, so it's filled in via
+    // `inner` once the rest of this function is done building.
+    let inner: std::sync::Arc<std::sync::OnceLock<Router>> = std::sync::Arc::new(std::sync::OnceLock::new());
+    let batch_inner = inner.clone();
+    router = router.route(
+        "/api/batch",
+        post(move |headers: HeaderMap, body: Json<crate::models::BatchRequest>| {
+            let batch_inner = batch_inner.clone();
+            async move {
+                let inner = batch_inner.get().expect("router fully built before first request").clone();
+                batch(inner, headers, body).await
+            }
+        }),
+    );
+    if let Some(threshold) = max_request_duration_warning {
+        router = router.route_layer(axum::middleware::from_fn(
+            move |req: axum::extract::Request, next: axum::middleware::Next| {
+                crate::middleware::max_request_duration_warning(threshold, req, next)
+            },
+        ));
+    }
+    if !disabled_routes.is_empty() {
+        router = router.route_layer(axum::middleware::from_fn(
+            move |req: axum::extract::Request, next: axum::middleware::Next| {
+                let disabled_routes = disabled_routes.clone();
+                async move { crate::middleware::disabled_routes(disabled_routes, req, next).await }
+            },
+        ));
+    }
+    if !deprecated_routes.is_empty() {
+        router = router.route_layer(axum::middleware::from_fn(
+            move |req: axum::extract::Request, next: axum::middleware::Next| {
+                let deprecated_routes = deprecated_routes.clone();
+                async move { crate::middleware::deprecation_header(deprecated_routes, req, next).await }
+            },
+        ));
+    }
+    if !cache_control_routes.is_empty() {
+        router = router.route_layer(axum::middleware::from_fn(
+            move |req: axum::extract::Request, next: axum::middleware::Next| {
+                let cache_control_routes = cache_control_routes.clone();
+                async move { crate::middleware::cache_control(cache_control_routes, req, next).await }
+            },
+        ));
+    }
+    if swagger_ui {
+        router = router.route("/docs", get(crate::docs::swagger_ui));
+    }
+    if let Some(body) = root_response {
+        router = router.route("/", get(move || async move { Json(body) }));
+    }
+    if let Some(body) = robots_txt {
+        router = router.route("/robots.txt", get(move || async move { body }));
+    }
+    if let Some(body) = security_txt {
+        router = router.route("/.well-known/security.txt", get(move || async move { body }));
+    }
+    if route_introspection {
+        router = router.route("/api/routes", get(crate::introspection::list_routes));
+    }
+    // `max_json_depth` and `detailed_json_errors` both buffer the whole
+    // body to inspect it, so they have to be registered — and therefore
+    // run — *inside* `request_body_limit`'s own cap (axum runs the
+    // most-recently-registered layer first); a later registration here
+    // would let either buffer an unbounded body before the size limit
+    // ever got a chance to reject it.
+    if let Some(max_depth) = max_json_depth {
+        router = router.layer(axum::middleware::from_fn(
+            move |req: axum::extract::Request, next: axum::middleware::Next| {
+                crate::middleware::max_json_depth(max_depth, req, next)
+            },
+        ));
+    }
+    if detailed_json_errors {
+        router = router.layer(axum::middleware::from_fn(
+            crate::middleware::detailed_json_errors,
+        ));
+    }
+    router = router.route_layer(axum::middleware::from_fn(
+        move |req: axum::extract::Request, next: axum::middleware::Next| {
+            crate::middleware::request_body_limit(
+                max_request_body_bytes,
+                max_article_body_bytes,
+                req,
+                next,
+            )
+        },
+    ));
+    router = router.layer(axum::middleware::from_fn(crate::middleware::correlation_id));
+    router = router.layer(axum::middleware::from_fn(
+        move |req: axum::extract::Request, next: axum::middleware::Next| {
+            crate::middleware::log_sampler(log_sample_rate, req, next)
+        },
+    ));
+    router = router.layer(axum::middleware::from_fn(crate::middleware::expect_continue));
+    router = router.layer(axum::middleware::from_fn(
+        move |req: axum::extract::Request, next: axum::middleware::Next| {
+            crate::middleware::api_version_header(api_version.clone(), req, next)
+        },
+    ));
+    if strip_headers {
+        router = router.layer(axum::middleware::from_fn(
+            crate::middleware::strip_response_headers,
+        ));
+    }
+    if let Some(hosts) = allowed_hosts {
+        router = router.layer(axum::middleware::from_fn(
+            move |req: axum::extract::Request, next: axum::middleware::Next| {
+                let hosts = hosts.clone();
+                async move { crate::middleware::allowed_hosts(hosts, req, next).await }
+            },
+        ));
+    }
+    if require_https {
+        router = router.layer(axum::middleware::from_fn(crate::middleware::require_https));
+    }
+    inner.set(router.clone()).ok();
+    router
+}
+
+/// Maximum number of sub-requests accepted by one `POST /api/batch` call.
+const MAX_BATCH_REQUESTS: usize = 20;
+
+/// Headers copied from the top-level `POST /api/batch` request onto every
+/// synthesized sub-request, so a batched `GET` sees the same caller
+/// identity and locale a top-level request to the same path would —
+/// otherwise every batched route runs as an anonymous, default-locale
+/// request regardless of how the caller authenticated.
+const FORWARDED_BATCH_HEADERS: &[HeaderName] = &[
+    axum::http::header::AUTHORIZATION,
+    axum::http::header::COOKIE,
+    axum::http::header::ACCEPT_LANGUAGE,
+];
+
+/// Dispatches each `GET` sub-request in `body` through `router` (a clone
+/// of the fully-built router, captured by [`build_router`] after every
+/// other route and layer is already in place — so a batched sub-request
+/// goes through the exact same middleware stack a top-level request
+/// would), and collects the results in order. Only `GET` is accepted;
+/// anything else comes back as its own `405` entry without being
+/// dispatched, since a batch is for reads, not for working around
+/// per-request write guards.
+async fn batch(router: Router, headers: HeaderMap, Json(body): Json<crate::models::BatchRequest>) -> Response {
+    if body.requests.len() > MAX_BATCH_REQUESTS {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            vec![format!("at most {MAX_BATCH_REQUESTS} requests may be batched at once")],
+        );
+    }
+    let mut results = Vec::with_capacity(body.requests.len());
+    for sub in body.requests {
+        if !sub.method.eq_ignore_ascii_case("get") {
+            results.push(crate::models::BatchSubResponse {
+                status: StatusCode::METHOD_NOT_ALLOWED.as_u16(),
+                body: serde_json::json!({"error": "only GET requests may be batched"}),
+            });
+            continue;
+        }
+        let mut builder = axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri(&sub.path);
+        for name in FORWARDED_BATCH_HEADERS {
+            if let Some(value) = headers.get(name) {
+                builder = builder.header(name, value);
+            }
+        }
+        let request = builder.body(axum::body::Body::empty());
+        let Ok(request) = request else {
+            results.push(crate::models::BatchSubResponse {
+                status: StatusCode::BAD_REQUEST.as_u16(),
+                body: serde_json::json!({"error": "invalid path"}),
+            });
+            continue;
+        };
+        let response = match tower::ServiceExt::oneshot(router.clone(), request).await {
+            Ok(response) => response,
+            Err(infallible) => match infallible {},
+        };
+        let status = response.status().as_u16();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap_or_default();
+        let body = serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null);
+        results.push(crate::models::BatchSubResponse { status, body });
+    }
+    Json(results).into_response()
+}
+
+async fn login<S: AppState>(
+    State(state): State<S>,
+    Json(body): Json<crate::models::LoginUserRequest>,
+) -> Response {
+    let lockout_enabled = state.server_config().max_login_attempts.is_some();
+    if lockout_enabled && state.is_locked(&body.user.email).await {
+        return StatusCode::LOCKED.into_response();
+    }
+    match state.login(body.user.clone()).await {
+        users::LoginResponse::Status200_OK(user) => with_rate_limit_headers(
+            Json(UserResponse { user }).into_response(),
+            Users::rate_limit_state(&state),
+        ),
+        users::LoginResponse::Status401_Unauthorized => {
+            if lockout_enabled {
+                state.record_login_failure(&body.user.email).await;
+            }
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        users::LoginResponse::Status422_UnprocessableEntity(errors) => {
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response()
+        }
+        users::LoginResponse::Status423_Locked { retry_after_secs } => match retry_after_secs {
+            Some(secs) => (
+                StatusCode::LOCKED,
+                [(axum::http::header::RETRY_AFTER, secs.to_string())],
+            )
+                .into_response(),
+            None => StatusCode::LOCKED.into_response(),
+        },
+    }
+}
+
+async fn register<S: AppState>(
+    State(state): State<S>,
+    Json(body): Json<NewUserRequest>,
+) -> Response {
+    let errors = if state.server_config().offload_validation {
+        let validation_state = state.clone();
+        let new_user = body.user.clone();
+        tokio::task::spawn_blocking(move || validation_state.create_user_validation(&new_user))
+            .await
+            .unwrap_or_else(|_| vec!["validation failed unexpectedly".to_string()])
+    } else {
+        state.create_user_validation(&body.user)
+    };
+    if !errors.is_empty() {
+        return error_response(StatusCode::UNPROCESSABLE_ENTITY, errors);
+    }
+    match state.register(body.user).await {
+        users::RegisterResponse::Status201_Created(user) => {
+            (StatusCode::CREATED, Json(UserResponse { user })).into_response()
+        }
+        users::RegisterResponse::Status409_Conflict(errors) => {
+            (StatusCode::CONFLICT, Json(errors)).into_response()
+        }
+        users::RegisterResponse::Status422_UnprocessableEntity(errors) => {
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response()
+        }
+    }
+}
+
+async fn current_user<S: AppState>(State(state): State<S>, headers: HeaderMap) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    match state.current_user(claims).await {
+        users::CurrentUserResponse::Status200_OK(user) => {
+            Json(UserResponse { user }).into_response()
+        }
+        users::CurrentUserResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+    }
+}
+
+async fn export_user_data<S: AppState>(State(state): State<S>, headers: HeaderMap) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    match state.export_user_data(claims).await {
+        users::ExportUserDataResponse::Status200_OK(export) => {
+            let case = state.server_config().json_case;
+            let max_response_bytes = state.server_config().max_response_bytes;
+            let mut response = if state.server_config().range_requests_on_user_export {
+                let body = crate::case::render(export, case);
+                if let Some(max) = max_response_bytes {
+                    if body.len() > max {
+                        tracing::error!(
+                            size = body.len(),
+                            max,
+                            "serialized response exceeds max_response_bytes"
+                        );
+                        return error_response(
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            vec!["response exceeded the configured maximum size".to_string()],
+                        );
+                    }
+                }
+                let range_header = headers.get(axum::http::header::RANGE).and_then(|v| v.to_str().ok());
+                let mut response = serve_with_optional_range(body.into_bytes(), range_header);
+                response.headers_mut().insert(
+                    axum::http::header::CONTENT_TYPE,
+                    axum::http::HeaderValue::from_static("application/json"),
+                );
+                response
+            } else {
+                render(export, case, max_response_bytes)
+            };
+            response.headers_mut().insert(
+                axum::http::header::CONTENT_DISPOSITION,
+                axum::http::HeaderValue::from_static("attachment; filename=\"account-data.json\""),
+            );
+            response
+        }
+        users::ExportUserDataResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+struct DeleteAccountParams {
+    confirm: Option<bool>,
+}
+
+/// Returns `400 Bad Request` unless `confirm` is explicitly `true`, so a
+/// bare `DELETE /api/user` (no query string, or `?confirm=false`) can't
+/// destroy an account by accident.
+fn require_delete_confirmation(confirm: Option<bool>) -> Option<Response> {
+    if confirm == Some(true) {
+        None
+    } else {
+        Some(error_response(
+            StatusCode::BAD_REQUEST,
+            vec!["pass ?confirm=true to delete your account".to_string()],
+        ))
+    }
+}
+
+/// Calls `state`'s [`Authorization::authorize`] for `operation`/`resource`
+/// with `claims`, returning `Some(403 Forbidden)` on denial or `None` to
+/// let the handler proceed — the same `Option<Response>`-short-circuit
+/// shape as [`require_if_match`] and [`require_delete_confirmation`].
+async fn require_authorization<S: AppState>(
+    state: &S,
+    claims: &Claims,
+    operation: Operation,
+    resource: ResourceId,
+) -> Option<Response> {
+    if state.authorize(claims, operation, resource).await {
+        None
+    } else {
+        Some(StatusCode::FORBIDDEN.into_response())
+    }
+}
+
+/// Returns `Some(503 Service Unavailable)` if `state`'s
+/// [`crate::context::ServerConfig::circuit_breaker`] is open, or `None`
+/// to let the handler proceed — called before every mutating handler's
+/// trait method, the same `Option<Response>`-short-circuit shape as
+/// [`require_authorization`]. A `None` breaker (the default) never
+/// short-circuits anything.
+#[cfg(feature = "circuit-breaker")]
+fn require_open_circuit<S: AppState>(state: &S) -> Option<Response> {
+    match &state.server_config().circuit_breaker {
+        Some(breaker) if breaker.is_open() => Some(StatusCode::SERVICE_UNAVAILABLE.into_response()),
+        _ => None,
+    }
+}
+
+/// Acquires a permit under `user_id` from `state`'s
+/// [`crate::context::ServerConfig::max_concurrent_favorites_per_user`]
+/// limiter, for `favorite_article`/`unfavorite_article`. `Ok(None)` means
+/// no limiter is configured; `Ok(Some(permit))` holds the caller's slot
+/// until the returned guard is dropped; `Err` is the `429` response to
+/// return immediately instead of calling the trait method.
+fn favorites_permit<S: AppState>(
+    state: &S,
+    user_id: &str,
+) -> Result<Option<tokio::sync::OwnedSemaphorePermit>, Response> {
+    match &state.server_config().max_concurrent_favorites_per_user {
+        None => Ok(None),
+        Some(limiter) => match limiter.try_acquire(user_id) {
+            Some(permit) => Ok(Some(permit)),
+            None => Err(StatusCode::TOO_MANY_REQUESTS.into_response()),
+        },
+    }
+}
+
+async fn delete_account<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Query(params): Query<DeleteAccountParams>,
+    body: axum::body::Bytes,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    if let Some(response) = require_delete_confirmation(params.confirm) {
+        return response;
+    }
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::DeleteAccount, ResourceId::Existing(claims.user_id.clone())).await
+    {
+        return response;
+    }
+    let password = if body.is_empty() {
+        None
+    } else {
+        match serde_json::from_slice::<DeleteAccountRequest>(&body) {
+            Ok(request) => request.password,
+            Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+        }
+    };
+    match state.delete_account(claims, password).await {
+        users::DeleteAccountResponse::Status204_NoContent => empty(StatusCode::NO_CONTENT),
+        users::DeleteAccountResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        users::DeleteAccountResponse::Status422_UnprocessableEntity(errors) => {
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response()
+        }
+    }
+}
+
+async fn update_user<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Json(body): Json<UpdateUserRequest>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    if let Some(response) = require_if_match(&headers, state.server_config().require_conditional_writes) {
+        return response;
+    }
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::UpdateUser, ResourceId::Existing(claims.user_id.clone())).await
+    {
+        return response;
+    }
+    if let Some(if_match) = headers.get(axum::http::header::IF_MATCH).and_then(|v| v.to_str().ok()) {
+        if let Some(etag) = state.current_user_etag(&claims).await {
+            if if_match != etag && if_match != "*" {
+                return StatusCode::PRECONDITION_FAILED.into_response();
+            }
+        }
+    }
+    #[cfg(feature = "password-strength")]
+    {
+        let errors = if state.server_config().offload_validation {
+            let validation_state = state.clone();
+            let update = body.user.clone();
+            tokio::task::spawn_blocking(move || validation_state.update_user_validation(&update))
+                .await
+                .unwrap_or_else(|_| vec!["validation failed unexpectedly".to_string()])
+        } else {
+            state.update_user_validation(&body.user)
+        };
+        if !errors.is_empty() {
+            return error_response(StatusCode::UNPROCESSABLE_ENTITY, errors);
+        }
+    }
+    if let Some(email) = &body.user.email {
+        if state.email_is_taken(&claims, email) {
+            return error_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                vec!["email has already been taken".to_string()],
+            );
+        }
+    }
+    match state.update_user(claims, body.user).await {
+        users::UpdateUserResponse::Status200_OK(user) => {
+            Json(UserResponse { user }).into_response()
+        }
+        users::UpdateUserResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        users::UpdateUserResponse::Status422_UnprocessableEntity(errors) => {
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response()
+        }
+        users::UpdateUserResponse::Status412_PreconditionFailed => {
+            StatusCode::PRECONDITION_FAILED.into_response()
+        }
+    }
+}
+
+async fn rotate_token<S: AppState>(State(state): State<S>, headers: HeaderMap) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::RotateToken, ResourceId::Existing(claims.user_id.clone())).await
+    {
+        return response;
+    }
+    match state.rotate_token(claims).await {
+        users::RotateTokenResponse::Status200_OK(user) => {
+            Json(UserResponse { user }).into_response()
+        }
+        users::RotateTokenResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+    }
+}
+
+async fn get_user_by_username<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(username): Path<String>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(claims) => claims,
+        Err(response) => return response,
+    };
+    match state.get_user_by_username(claims, username).await {
+        users::GetUserByUsernameResponse::Status200_OK(user) => {
+            Json(crate::models::PublicUserResponse { user }).into_response()
+        }
+        users::GetUserByUsernameResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
+
+/// Builds `self`/`next`/`prev`/`first`/`last` pagination links for
+/// `GET /api/articles` from the request's `limit`/`offset` and the total
+/// result count.
+fn build_pagination_links(
+    params: &ListArticlesParams,
+    articles_count: i64,
+) -> crate::models::PaginationLinks {
+    let limit = params.limit.unwrap_or(20).max(1);
+    let offset = params.offset.unwrap_or(0).max(0);
+    let link_for = |offset: i64| format!("/api/articles?limit={}&offset={}", limit, offset);
+    let next = if offset + limit < articles_count {
+        Some(link_for(offset + limit))
+    } else {
+        None
+    };
+    let prev = if offset > 0 {
+        Some(link_for((offset - limit).max(0)))
+    } else {
+        None
+    };
+    let last_offset = ((articles_count - 1) / limit).max(0) * limit;
+    crate::models::PaginationLinks {
+        self_: link_for(offset),
+        next,
+        prev,
+        first: link_for(0),
+        last: if articles_count > 0 {
+            Some(link_for(last_offset))
+        } else {
+            None
+        },
+    }
+}
+
+/// In debug builds, logs a `tracing::warn!` if `article`'s favorite
+/// bookkeeping looks internally inconsistent: `favorited: true` paired
+/// with `favorites_count == 0`, or a negative `favorites_count`. Either
+/// is an implementor bug, not something a client sent, so this never
+/// fails the request — it just surfaces the bug during development.
+/// Compiles to nothing in release builds.
+fn check_favorited_consistency(article: &crate::models::Article) {
+    #[cfg(debug_assertions)]
+    {
+        if article.favorited.as_bool() && article.favorites_count == 0 {
+            tracing::warn!(
+                slug = %article.slug,
+                "article reports favorited=true but favorites_count=0"
+            );
+        }
+        if article.favorites_count < 0 {
+            tracing::warn!(
+                slug = %article.slug,
+                favorites_count = article.favorites_count,
+                "article has a negative favorites_count"
+            );
+        }
+    }
+}
+
+/// If [`crate::context::ServerConfig::strict_timestamps`] is set, logs a
+/// `tracing::warn!` when `created_at`/`updated_at` is further in the
+/// future than `now + tolerance` allows for clock skew. This never
+/// rejects the response — it's a data-sanity guard for an implementor
+/// bug (a bad clock, a timezone mix-up), not something a client caused.
+fn check_timestamp_sanity(
+    label: &str,
+    id: &str,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+    tolerance: std::time::Duration,
+    now: chrono::DateTime<chrono::Utc>,
+) {
+    let cutoff = now + chrono::Duration::from_std(tolerance).unwrap_or(chrono::Duration::zero());
+    if created_at > cutoff {
+        tracing::warn!(kind = label, id, %created_at, "created_at is in the future");
+    }
+    if updated_at > cutoff {
+        tracing::warn!(kind = label, id, %updated_at, "updated_at is in the future");
+    }
+}
+
+fn check_article_timestamps<S: AppState>(state: &S, article: &crate::models::Article) {
+    if let Some(tolerance) = state.server_config().strict_timestamps {
+        check_timestamp_sanity(
+            "article",
+            &article.slug,
+            article.created_at,
+            article.updated_at,
+            tolerance,
+            state.server_config().clock.now(),
+        );
+    }
+}
+
+/// Substitutes `default` for `profile.image` when it's unset or blank.
+/// See [`crate::context::ServerConfig::default_avatar_url`].
+fn apply_default_avatar(profile: &mut crate::models::Profile, default: &Option<String>) {
+    if let Some(default) = default {
+        if profile.image.as_deref().unwrap_or("").is_empty() {
+            profile.image = Some(default.clone());
+        }
+    }
+}
+
+fn apply_default_avatar_to_article<S: AppState>(state: &S, article: &mut crate::models::Article) {
+    apply_default_avatar(&mut article.author, &state.server_config().default_avatar_url);
+}
+
+fn apply_default_avatar_to_comment<S: AppState>(state: &S, comment: &mut crate::models::Comment) {
+    apply_default_avatar(&mut comment.author, &state.server_config().default_avatar_url);
+}
+
+fn check_comment_timestamps<S: AppState>(state: &S, comment: &crate::models::Comment) {
+    if let Some(tolerance) = state.server_config().strict_timestamps {
+        check_timestamp_sanity(
+            "comment",
+            &comment.id.to_string(),
+            comment.created_at,
+            comment.updated_at,
+            tolerance,
+            state.server_config().clock.now(),
+        );
+    }
+}
+
+async fn list_articles<S: AppState>(
+    State(state): State<S>,
+    params: Result<Query<ListArticlesParams>, axum::extract::rejection::QueryRejection>,
+) -> Response {
+    let params = match query_or_default(params, state.server_config().query_param_strict) {
+        Ok(params) => params,
+        Err(response) => return response,
+    };
+    let params = match trim_list_articles_params(params) {
+        Ok(params) => params,
+        Err(response) => return response,
+    };
+    let warning = state.warning();
+    let partial_personalization = Articles::partial_personalization(&state);
+    let pagination_links = state.server_config().pagination_links;
+    let links = pagination_links.then(|| params.clone());
+    match state.list_articles(params).await {
+        crate::apis::articles::ListArticlesResponse::Status200_OK(mut articles, articles_count) => {
+            articles.iter().for_each(check_favorited_consistency);
+            articles.iter().for_each(|a| check_article_timestamps(&state, a));
+            articles
+                .iter_mut()
+                .for_each(|a| apply_default_avatar_to_article(&state, a));
+            let links = links.map(|params| build_pagination_links(&params, articles_count));
+            let body = if state.server_config().json_api {
+                let items = articles
+                    .into_iter()
+                    .map(|article| {
+                        (
+                            article.slug.clone(),
+                            serde_json::to_value(&article).unwrap_or_default(),
+                        )
+                    })
+                    .collect();
+                crate::jsonapi::response(crate::jsonapi::collection("article", items))
+            } else {
+                render(
+                    crate::models::MultipleArticles {
+                        articles,
+                        articles_count,
+                        links,
+                    },
+                    state.server_config().json_case,
+                    state.server_config().max_response_bytes,
+                )
+            };
+            with_partial_personalization_header(with_warning_header(body, warning), partial_personalization)
+        }
+    }
+}
+
+async fn draft_articles<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    params: Result<Query<ListArticlesParams>, axum::extract::rejection::QueryRejection>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    let params = match query_or_default(params, state.server_config().query_param_strict) {
+        Ok(params) => params,
+        Err(response) => return response,
+    };
+    let params = match trim_list_articles_params(params) {
+        Ok(params) => params,
+        Err(response) => return response,
+    };
+    match state.draft_articles(claims, params).await {
+        crate::apis::articles::DraftArticlesResponse::Status200_OK(mut articles, articles_count) => {
+            articles.iter().for_each(check_favorited_consistency);
+            articles.iter().for_each(|a| check_article_timestamps(&state, a));
+            articles
+                .iter_mut()
+                .for_each(|a| apply_default_avatar_to_article(&state, a));
+            render(
+                crate::models::MultipleArticles {
+                    articles,
+                    articles_count,
+                    links: None,
+                },
+                state.server_config().json_case,
+                state.server_config().max_response_bytes,
+            )
+        }
+        crate::apis::articles::DraftArticlesResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+    }
+}
+
+async fn leaderboard<S: AppState>(
+    State(state): State<S>,
+    params: Result<Query<crate::apis::articles::LeaderboardParams>, axum::extract::rejection::QueryRejection>,
+) -> Response {
+    let params = match query_or_default(params, state.server_config().query_param_strict) {
+        Ok(params) => params,
+        Err(response) => return response,
+    };
+    match state.leaderboard(params.limit.unwrap_or(20)).await {
+        crate::apis::articles::LeaderboardResponse::Status200_OK(entries) => {
+            Json(entries).into_response()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+struct ChangesSinceParams {
+    since: Option<i64>,
+    limit: Option<i64>,
+}
+
+async fn changes_since<S: AppState>(
+    State(state): State<S>,
+    params: Result<Query<ChangesSinceParams>, axum::extract::rejection::QueryRejection>,
+) -> Response {
+    let params = match query_or_default(params, state.server_config().query_param_strict) {
+        Ok(params) => params,
+        Err(response) => return response,
+    };
+    let since = params.since.unwrap_or(0);
+    if since < 0 {
+        return error_response(StatusCode::BAD_REQUEST, vec!["since must be >= 0".to_string()]);
+    }
+    match state.changes_since(since, params.limit.unwrap_or(20)).await {
+        crate::apis::articles::ChangesSinceResponse::Status200_OK(changes, next_since) => {
+            render(
+                crate::models::ArticleChanges { changes, next_since },
+                state.server_config().json_case,
+                state.server_config().max_response_bytes,
+            )
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+struct SlugifyParams {
+    title: Option<String>,
+}
+
+/// `GET /api/articles/slugify`: previews the slug `title` would get from
+/// [`crate::apis::articles::slugify`], without creating an article. No
+/// auth required. Registered ahead of `/api/articles/:slug` so `slugify`
+/// is never captured as a slug path parameter.
+async fn slugify_title<S: AppState>(
+    State(state): State<S>,
+    params: Result<Query<SlugifyParams>, axum::extract::rejection::QueryRejection>,
+) -> Response {
+    let params = match query_or_default(params, state.server_config().query_param_strict) {
+        Ok(params) => params,
+        Err(response) => return response,
+    };
+    let title = params.title.unwrap_or_default();
+    let errors = crate::apis::articles::title_validation(&title);
+    if !errors.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, errors);
+    }
+    Json(serde_json::json!({ "slug": crate::apis::articles::slugify(&title) })).into_response()
+}
+
+/// `GET /api/articles/options`: the allowed query param values for `GET
+/// /api/articles`, derived from [`crate::apis::articles::ArticleSort`]
+/// rather than hand-maintained, so a client's dynamic sort-picker UI
+/// never drifts from what the enum actually accepts. No auth required.
+async fn list_articles_options() -> Response {
+    Json(crate::apis::articles::ListArticlesOptions {
+        sort: crate::apis::articles::ArticleSort::ALL
+            .iter()
+            .map(crate::apis::articles::ArticleSort::as_str)
+            .collect(),
+    })
+    .into_response()
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+struct GetArticleQuery {
+    /// `?format=html` renders `body` to HTML via
+    /// [`Articles::render_body_html`] before serializing, instead of
+    /// returning the raw markdown. Any other value (including absent)
+    /// leaves `body` as stored.
+    format: Option<String>,
+    /// `?profile=amp` returns [`crate::models::AmpArticle`] instead of
+    /// the full [`SingleArticle`] envelope. Any other value (including
+    /// absent) returns the full representation as usual.
+    profile: Option<String>,
+}
+
+async fn get_article<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(slug): Path<String>,
+    query: Result<Query<GetArticleQuery>, axum::extract::rejection::QueryRejection>,
+) -> Response {
+    if !state.slug_is_allowed(&slug) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let query = match query_or_default(query, state.server_config().query_param_strict) {
+        Ok(query) => query,
+        Err(response) => return response,
+    };
+    let warning = state.warning();
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(claims) => claims,
+        Err(response) => return response,
+    };
+    let can_edit_claims = claims.clone();
+    match state.get_article(claims, slug).await {
+        crate::apis::articles::GetArticleResponse::Status200_OK(mut article) => {
+            check_favorited_consistency(&article);
+            check_article_timestamps(&state, &article);
+            apply_default_avatar_to_article(&state, &mut article);
+            article.can_edit = Some(state.can_edit(can_edit_claims.as_ref(), &article));
+            if query.format.as_deref() == Some("html") {
+                if let Some(html) = state.render_body_html(&article.body) {
+                    article.body = html;
+                }
+            }
+            if query.profile.as_deref() == Some("amp") {
+                let body = Json(crate::models::AmpArticle::from(article)).into_response();
+                return with_warning_header(body, warning);
+            }
+            let body = if state.server_config().json_api {
+                let id = article.slug.clone();
+                crate::jsonapi::response(crate::jsonapi::single(
+                    "article",
+                    &id,
+                    serde_json::to_value(&article).unwrap_or_default(),
+                ))
+            } else {
+                render(SingleArticle { article }, state.server_config().json_case, state.server_config().max_response_bytes)
+            };
+            with_warning_header(body, warning)
+        }
+        crate::apis::articles::GetArticleResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+        crate::apis::articles::GetArticleResponse::Status410_Gone => {
+            StatusCode::GONE.into_response()
+        }
+    }
+}
+
+/// Serves `GET /api/articles/:slug/oembed`: an [oEmbed](https://oembed.com)
+/// `"link"`-type representation of the article, for consumers (chat apps,
+/// blogging platforms) that unfurl links via oEmbed discovery rather than
+/// scraping Open Graph tags.
+async fn oembed_article<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(slug): Path<String>,
+) -> Response {
+    if !state.slug_is_allowed(&slug) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(claims) => claims,
+        Err(response) => return response,
+    };
+    match state.get_article(claims, slug).await {
+        crate::apis::articles::GetArticleResponse::Status200_OK(article) => {
+            Json(crate::models::OEmbed {
+                type_: "link",
+                version: "1.0",
+                title: article.title,
+                author_name: article.author.username.clone(),
+                author_url: format!("/api/profiles/{}", article.author.username),
+                provider_name: "learn-language",
+            })
+            .into_response()
+        }
+        crate::apis::articles::GetArticleResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+        crate::apis::articles::GetArticleResponse::Status410_Gone => {
+            StatusCode::GONE.into_response()
+        }
+    }
+}
+
+/// Parses a single-range `bytes=start-end` `Range` header value into
+/// `(start, end)` inclusive byte offsets, clamped to `len`. Multi-range
+/// requests and unsatisfiable ranges return `None`, which the caller
+/// treats as "serve the whole body".
+fn parse_byte_range(header: &str, len: usize) -> Option<(usize, usize)> {
+    let spec = header.strip_prefix("bytes=")?;
+    if spec.contains(',') {
+        return None;
+    }
+    let (start, end) = spec.split_once('-')?;
+    let len = len as i64;
+    let (start, end) = if start.is_empty() {
+        let suffix_len: i64 = end.parse().ok()?;
+        (len - suffix_len, len - 1)
+    } else {
+        let start: i64 = start.parse().ok()?;
+        let end = if end.is_empty() {
+            len - 1
+        } else {
+            end.parse().ok()?
+        };
+        (start, end)
+    };
+    if start < 0 || end < start || start >= len {
+        return None;
+    }
+    Some((start.max(0) as usize, end.min(len - 1) as usize))
+}
+
+/// Serves `bytes` as a `200 OK` with `Accept-Ranges: bytes`, or as a `206
+/// Partial Content` slice plus `Content-Range` if `range_header` (the raw
+/// `Range` header value, if any) names a single satisfiable range.
+/// Multi-range and unsatisfiable requests fall back to serving the whole
+/// body, same as no `Range` header at all — shared by every export
+/// endpoint that wants resumable downloads.
+fn serve_with_optional_range(bytes: Vec<u8>, range_header: Option<&str>) -> Response {
+    match range_header.and_then(|h| parse_byte_range(h, bytes.len())) {
+        Some((start, end)) => {
+            let chunk = bytes[start..=end].to_vec();
+            let content_range = format!("bytes {}-{}/{}", start, end, bytes.len());
+            (
+                StatusCode::PARTIAL_CONTENT,
+                [
+                    (axum::http::header::CONTENT_RANGE, content_range),
+                    (axum::http::header::ACCEPT_RANGES, "bytes".to_string()),
+                ],
+                chunk,
+            )
+                .into_response()
+        }
+        None => (
+            StatusCode::OK,
+            [(axum::http::header::ACCEPT_RANGES, "bytes".to_string())],
+            bytes,
+        )
+            .into_response(),
+    }
+}
+
+async fn export_article<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(slug): Path<String>,
+) -> Response {
+    let body = match state.export_article(slug).await {
+        crate::apis::articles::ExportArticleResponse::Status200_OK(body) => body,
+        crate::apis::articles::ExportArticleResponse::Status404_NotFound => {
+            return StatusCode::NOT_FOUND.into_response();
+        }
+        crate::apis::articles::ExportArticleResponse::Status202_Accepted { status_url } => {
+            return accepted(status_url);
+        }
+    };
+    let range_header = headers.get("range").and_then(|v| v.to_str().ok());
+    serve_with_optional_range(body.into_bytes(), range_header)
+}
+
+/// The caller's highest-priority language tag from an `Accept-Language`
+/// header, without attempting full RFC 4647 weight-ordered negotiation —
+/// just the first tag before any `,` or `;q=`.
+fn primary_locale(headers: &HeaderMap) -> Option<String> {
+    let value = headers.get(axum::http::header::ACCEPT_LANGUAGE)?.to_str().ok()?;
+    let first = value.split(',').next()?.split(';').next()?.trim();
+    if first.is_empty() {
+        None
+    } else {
+        Some(first.to_string())
+    }
+}
+
+async fn create_article<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Json(mut body): Json<NewArticleRequest>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::CreateArticle, ResourceId::New).await
+    {
+        return response;
+    }
+    body.article.title = body.article.title.trim().to_string();
+    let reject_duplicate_tags = state.server_config().reject_duplicate_tags;
+    if !reject_duplicate_tags {
+        if let Some(tag_list) = body.article.tag_list.take() {
+            body.article.tag_list = Some(dedupe_tags(tag_list));
+        }
+    }
+    let mut errors = state.create_article_validation(&body.article);
+    if let Some(tag_list) = &body.article.tag_list {
+        for tag in tag_list {
+            errors.extend(state.tag_validation(tag));
+        }
+        errors.extend(check_allowed_tags(tag_list, &state.server_config().allowed_tags));
+        if reject_duplicate_tags {
+            errors.extend(check_duplicate_tags(tag_list));
+        }
+    }
+    if !errors.is_empty() {
+        return error_response(StatusCode::UNPROCESSABLE_ENTITY, errors);
+    }
+    let locale = primary_locale(&headers);
+    match state.create_article(claims, body.article, locale).await {
+        crate::apis::articles::CreateArticleResponse::Status201_Created(mut article) => {
+            check_favorited_consistency(&article);
+            check_article_timestamps(&state, &article);
+            apply_default_avatar_to_article(&state, &mut article);
+            state
+                .dispatch(crate::apis::webhooks::ArticleEvent::Created(article.clone()))
+                .await;
+            with_rate_limit_headers(
+                (StatusCode::CREATED, Json(SingleArticle { article })).into_response(),
+                Articles::rate_limit_state(&state),
+            )
+        }
+        crate::apis::articles::CreateArticleResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        crate::apis::articles::CreateArticleResponse::Status422_UnprocessableEntity(errors) => {
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response()
+        }
+    }
+}
+
+async fn bulk_import_articles<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Json(body): Json<crate::models::BulkImportArticlesRequest>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::BulkImportArticles, ResourceId::New).await
+    {
+        return response;
+    }
+    match state.bulk_import_articles(claims, body.articles).await {
+        crate::apis::articles::BulkImportArticlesResponse::Status207_MultiStatus(mut results) => {
+            for result in &mut results {
+                if let crate::apis::articles::BulkImportResult::Created(article) = result {
+                    apply_default_avatar_to_article(&state, article);
+                }
+            }
+            (StatusCode::MULTI_STATUS, Json(results)).into_response()
+        }
+        crate::apis::articles::BulkImportArticlesResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+    }
+}
+
+async fn update_article<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(slug): Path<String>,
+    Json(mut body): Json<UpdateArticleRequest>,
+) -> Response {
+    if !state.slug_is_allowed(&slug) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    if let Some(response) = require_if_match(&headers, state.server_config().require_conditional_writes) {
+        return response;
+    }
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::UpdateArticle, ResourceId::Existing(slug.clone())).await
+    {
+        return response;
+    }
+    if let Some(title) = &body.article.title {
+        body.article.title = Some(title.trim().to_string());
+    }
+    let errors = state.update_article_validation(&body.article);
+    if !errors.is_empty() {
+        return error_response(StatusCode::UNPROCESSABLE_ENTITY, errors);
+    }
+    match state.update_article(claims, slug, body.article).await {
+        crate::apis::articles::UpdateArticleResponse::Status200_OK(mut article) => {
+            check_favorited_consistency(&article);
+            check_article_timestamps(&state, &article);
+            apply_default_avatar_to_article(&state, &mut article);
+            state
+                .dispatch(crate::apis::webhooks::ArticleEvent::Updated(article.clone()))
+                .await;
+            render(SingleArticle { article }, state.server_config().json_case, state.server_config().max_response_bytes)
+        }
+        crate::apis::articles::UpdateArticleResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        crate::apis::articles::UpdateArticleResponse::Status403_Forbidden => {
+            StatusCode::FORBIDDEN.into_response()
+        }
+        crate::apis::articles::UpdateArticleResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+        crate::apis::articles::UpdateArticleResponse::Status422_UnprocessableEntity(errors) => {
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response()
+        }
+    }
+}
+
+async fn delete_article<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(slug): Path<String>,
+) -> Response {
+    if !state.slug_is_allowed(&slug) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    if let Some(response) = require_if_match(&headers, state.server_config().require_conditional_writes) {
+        return response;
+    }
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::DeleteArticle, ResourceId::Existing(slug.clone())).await
+    {
+        return response;
+    }
+    let deleted_slug = slug.clone();
+    match state.delete_article(claims, slug).await {
+        crate::apis::articles::DeleteArticleResponse::Status200_OK => {
+            state
+                .dispatch(crate::apis::webhooks::ArticleEvent::Deleted { slug: deleted_slug })
+                .await;
+            empty(StatusCode::OK)
+        }
+        crate::apis::articles::DeleteArticleResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        crate::apis::articles::DeleteArticleResponse::Status403_Forbidden => {
+            StatusCode::FORBIDDEN.into_response()
+        }
+        crate::apis::articles::DeleteArticleResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+        crate::apis::articles::DeleteArticleResponse::Status202_Accepted { status_url } => {
+            accepted(status_url)
+        }
+    }
+}
+
+async fn favorite_article<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(slug): Path<String>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::FavoriteArticle, ResourceId::Existing(slug.clone())).await
+    {
+        return response;
+    }
+    let _permit = match favorites_permit(&state, &claims.user_id) {
+        Ok(permit) => permit,
+        Err(response) => return response,
+    };
+    match state.favorite_article(claims, slug).await {
+        crate::apis::articles::FavoriteArticleResponse::Status200_OK(mut article) => {
+            check_favorited_consistency(&article);
+            check_article_timestamps(&state, &article);
+            apply_default_avatar_to_article(&state, &mut article);
+            render(SingleArticle { article }, state.server_config().json_case, state.server_config().max_response_bytes)
+        }
+        crate::apis::articles::FavoriteArticleResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        crate::apis::articles::FavoriteArticleResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
+
+async fn unfavorite_article<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(slug): Path<String>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::UnfavoriteArticle, ResourceId::Existing(slug.clone())).await
+    {
+        return response;
+    }
+    let _permit = match favorites_permit(&state, &claims.user_id) {
+        Ok(permit) => permit,
+        Err(response) => return response,
+    };
+    match state.unfavorite_article(claims, slug).await {
+        crate::apis::articles::UnfavoriteArticleResponse::Status200_OK(mut article) => {
+            check_favorited_consistency(&article);
+            check_article_timestamps(&state, &article);
+            apply_default_avatar_to_article(&state, &mut article);
+            render(SingleArticle { article }, state.server_config().json_case, state.server_config().max_response_bytes)
+        }
+        crate::apis::articles::UnfavoriteArticleResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        crate::apis::articles::UnfavoriteArticleResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
+
+async fn delete_comment<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path((slug, id)): Path<(String, i64)>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::DeleteComment, ResourceId::Existing(id.to_string())).await
+    {
+        return response;
+    }
+    match state.delete_comment(claims, slug, id).await {
+        crate::apis::comments::DeleteCommentResponse::Status200_OK => empty(StatusCode::OK),
+        crate::apis::comments::DeleteCommentResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        crate::apis::comments::DeleteCommentResponse::Status403_Forbidden => {
+            StatusCode::FORBIDDEN.into_response()
+        }
+        crate::apis::comments::DeleteCommentResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
+
+async fn add_comment<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(slug): Path<String>,
+    Json(body): Json<NewCommentRequest>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::AddComment, ResourceId::Existing(slug.clone())).await
+    {
+        return response;
+    }
+    let max_comments_per_article = state.server_config().max_comments_per_article;
+    match state.add_comment(claims, slug, body.comment, max_comments_per_article).await {
+        crate::apis::comments::AddCommentResponse::Status200_OK(mut comment) => {
+            check_comment_timestamps(&state, &comment);
+            apply_default_avatar_to_comment(&state, &mut comment);
+            Json(SingleComment { comment }).into_response()
+        }
+        crate::apis::comments::AddCommentResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        crate::apis::comments::AddCommentResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+        crate::apis::comments::AddCommentResponse::Status409_Conflict(errors) => {
+            (StatusCode::CONFLICT, Json(errors)).into_response()
+        }
+        crate::apis::comments::AddCommentResponse::Status422_UnprocessableEntity(errors) => {
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response()
+        }
+    }
+}
+
+/// Maximum number of ids accepted by one `DELETE
+/// /api/articles/:slug/comments` bulk-delete request.
+const MAX_BULK_COMMENT_DELETE: usize = 50;
+
+async fn delete_comments<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(slug): Path<String>,
+    Query(params): Query<DeleteCommentsQuery>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    let Some(ids) = &params.ids else {
+        return error_response(StatusCode::BAD_REQUEST, vec!["ids is required".to_string()]);
+    };
+    let mut comment_ids = Vec::new();
+    for id in ids.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+        match id.parse::<i64>() {
+            Ok(id) => comment_ids.push(id),
+            Err(_) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    vec![format!("{id} is not a valid comment id")],
+                );
+            }
+        }
+    }
+    if comment_ids.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, vec!["ids is required".to_string()]);
+    }
+    if comment_ids.len() > MAX_BULK_COMMENT_DELETE {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            vec![format!("at most {MAX_BULK_COMMENT_DELETE} ids may be deleted at once")],
+        );
+    }
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::DeleteComments, ResourceId::Existing(slug.clone())).await
+    {
+        return response;
+    }
+    match state.delete_comments(claims, slug, comment_ids).await {
+        crate::apis::comments::DeleteCommentsResponse::Status200_OK(results) => {
+            Json(results).into_response()
+        }
+        crate::apis::comments::DeleteCommentsResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        crate::apis::comments::DeleteCommentsResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+struct DeleteCommentsQuery {
+    ids: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+struct RecentCommentsParams {
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+/// `GET /api/comments/recent`: the most recent comments across every
+/// article, for a site-wide activity feed. Authentication is optional,
+/// the same as `GET /api/users/:username` — present only so
+/// implementors can personalize each comment author's `following` flag
+/// for the caller.
+async fn get_recent_comments<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    params: Result<Query<RecentCommentsParams>, axum::extract::rejection::QueryRejection>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(claims) => claims,
+        Err(response) => return response,
+    };
+    let params = match query_or_default(params, state.server_config().query_param_strict) {
+        Ok(params) => params,
+        Err(response) => return response,
+    };
+    let limit = params.limit.unwrap_or(20);
+    let offset = params.offset.unwrap_or(0);
+    if limit < 0 {
+        return error_response(StatusCode::BAD_REQUEST, vec!["limit must be >= 0".to_string()]);
+    }
+    if offset < 0 {
+        return error_response(StatusCode::BAD_REQUEST, vec!["offset must be >= 0".to_string()]);
+    }
+    match state.get_recent_comments(claims, limit, offset).await {
+        crate::apis::comments::GetRecentCommentsResponse::Status200_OK(mut comments) => {
+            for recent in &mut comments {
+                check_comment_timestamps(&state, &recent.comment);
+                apply_default_avatar_to_comment(&state, &mut recent.comment);
+            }
+            Json(crate::models::RecentComments { comments }).into_response()
+        }
+    }
+}
+
+async fn get_comments<S: AppState>(State(state): State<S>, Path(slug): Path<String>) -> Response {
+    let partial_personalization = Comments::partial_personalization(&state);
+    match state.get_comments(slug).await {
+        crate::apis::comments::GetCommentsResponse::Status200_OK(mut comments) => {
+            comments.iter().for_each(|c| check_comment_timestamps(&state, c));
+            comments
+                .iter_mut()
+                .for_each(|c| apply_default_avatar_to_comment(&state, c));
+            with_partial_personalization_header(
+                Json(crate::models::MultipleComments { comments }).into_response(),
+                partial_personalization,
+            )
+        }
+        crate::apis::comments::GetCommentsResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
+
+async fn get_profile<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(username): Path<String>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(claims) => claims,
+        Err(response) => return response,
+    };
+    match state.get_profile(claims, username).await {
+        crate::apis::profiles::GetProfileResponse::Status200_OK(mut profile) => {
+            apply_default_avatar(&mut profile, &state.server_config().default_avatar_url);
+            if state.server_config().json_api {
+                let id = profile.username.clone();
+                crate::jsonapi::response(crate::jsonapi::single(
+                    "profile",
+                    &id,
+                    serde_json::to_value(&profile).unwrap_or_default(),
+                ))
+            } else {
+                Json(ProfileResponse { profile }).into_response()
+            }
+        }
+        crate::apis::profiles::GetProfileResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
+
+async fn profile_summary<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(username): Path<String>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(claims) => claims,
+        Err(response) => return response,
+    };
+    match state.profile_summary(claims, username).await {
+        crate::apis::profiles::ProfileSummaryResponse::Status200_OK(mut profile, mut articles) => {
+            apply_default_avatar(&mut profile, &state.server_config().default_avatar_url);
+            articles
+                .iter_mut()
+                .for_each(|a| apply_default_avatar_to_article(&state, a));
+            Json(crate::models::ProfileSummary { profile, articles }).into_response()
+        }
+        crate::apis::profiles::ProfileSummaryResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
+
+async fn author_tags<S: AppState>(State(state): State<S>, Path(username): Path<String>) -> Response {
+    match state.author_tags(username).await {
+        crate::apis::tags::AuthorTagsResponse::Status200_OK(tags) => {
+            Json(TagList { tags }).into_response()
+        }
+        crate::apis::tags::AuthorTagsResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
+
+async fn follow_user<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(username): Path<String>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::FollowProfile, ResourceId::Existing(username.clone())).await
+    {
+        return response;
+    }
+    match state.follow_user(claims, username).await {
+        crate::apis::profiles::FollowResponse::Status200_OK(mut profile) => {
+            apply_default_avatar(&mut profile, &state.server_config().default_avatar_url);
+            Json(ProfileResponse { profile }).into_response()
+        }
+        crate::apis::profiles::FollowResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        crate::apis::profiles::FollowResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
+
+async fn unfollow_user<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(username): Path<String>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::UnfollowProfile, ResourceId::Existing(username.clone())).await
+    {
+        return response;
+    }
+    match state.unfollow_user(claims, username).await {
+        crate::apis::profiles::UnfollowResponse::Status200_OK(mut profile) => {
+            apply_default_avatar(&mut profile, &state.server_config().default_avatar_url);
+            Json(ProfileResponse { profile }).into_response()
+        }
+        crate::apis::profiles::UnfollowResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        crate::apis::profiles::UnfollowResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
+
+async fn set_follow<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(username): Path<String>,
+    Json(body): Json<crate::models::SetFollowRequest>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    let operation = if body.following {
+        Operation::FollowProfile
+    } else {
+        Operation::UnfollowProfile
+    };
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, operation, ResourceId::Existing(username.clone())).await
+    {
+        return response;
+    }
+    match state.set_follow(claims, username, body.following).await {
+        crate::apis::profiles::SetFollowResponse::Status200_OK(mut profile) => {
+            apply_default_avatar(&mut profile, &state.server_config().default_avatar_url);
+            Json(ProfileResponse { profile }).into_response()
+        }
+        crate::apis::profiles::SetFollowResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        crate::apis::profiles::SetFollowResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
+
+async fn rename_tag<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(tag): Path<String>,
+    Json(body): Json<crate::models::RenameTagRequest>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    let mut errors = state.tag_validation(&body.tag);
+    errors.extend(check_allowed_tags(
+        std::slice::from_ref(&body.tag),
+        &state.server_config().allowed_tags,
+    ));
+    if !errors.is_empty() {
+        return error_response(StatusCode::UNPROCESSABLE_ENTITY, errors);
+    }
+    let warnings = if state.server_config().validation_warnings {
+        state.tag_warnings(&body.tag)
+    } else {
+        Vec::new()
+    };
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::RenameTag, ResourceId::Existing(tag.clone())).await
+    {
+        return response;
+    }
+    match state.rename_tag(claims, tag, body.tag).await {
+        crate::apis::tags::RenameTagResponse::Status200_OK => {
+            with_warnings_header(empty(StatusCode::OK), warnings)
+        }
+        crate::apis::tags::RenameTagResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        crate::apis::tags::RenameTagResponse::Status403_Forbidden => {
+            StatusCode::FORBIDDEN.into_response()
+        }
+        crate::apis::tags::RenameTagResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+        crate::apis::tags::RenameTagResponse::Status422_UnprocessableEntity(errors) => {
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response()
+        }
+    }
+}
+
+async fn delete_tag<S: AppState>(
+    State(state): State<S>,
+    headers: HeaderMap,
+    Path(tag): Path<String>,
+) -> Response {
+    let claims = match claims_from_headers(&state, &headers).await {
+        Ok(Some(claims)) => claims,
+        Ok(None) => return StatusCode::UNAUTHORIZED.into_response(),
+        Err(response) => return response,
+    };
+    #[cfg(feature = "circuit-breaker")]
+    if let Some(response) = require_open_circuit(&state) {
+        return response;
+    }
+    if let Some(response) =
+        require_authorization(&state, &claims, Operation::DeleteTag, ResourceId::Existing(tag.clone())).await
+    {
+        return response;
+    }
+    match state.delete_tag(claims, tag).await {
+        crate::apis::tags::DeleteTagResponse::Status200_OK => empty(StatusCode::OK),
+        crate::apis::tags::DeleteTagResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+        crate::apis::tags::DeleteTagResponse::Status403_Forbidden => {
+            StatusCode::FORBIDDEN.into_response()
+        }
+        crate::apis::tags::DeleteTagResponse::Status404_NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
+
+async fn get_tags<S: AppState>(State(state): State<S>) -> Response {
+    match state.get_tags().await {
+        crate::apis::tags::GetTagsResponse::Status200_OK(tags) => {
+            Json(TagList { tags }).into_response()
+        }
+    }
+}
+
+async fn popular_tags<S: AppState>(
+    State(state): State<S>,
+    params: Result<Query<crate::apis::tags::PopularTagsParams>, axum::extract::rejection::QueryRejection>,
+) -> Response {
+    let params = match query_or_default(params, state.server_config().query_param_strict) {
+        Ok(params) => params,
+        Err(response) => return response,
+    };
+    match state.popular_tags(params.window_days).await {
+        crate::apis::tags::PopularTagsResponse::Status200_OK(counts) => {
+            Json(counts).into_response()
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct ProfileResponse {
+    profile: crate::models::Profile,
+}
+
+async fn get_stats<S: AppState>(State(state): State<S>, headers: HeaderMap) -> Response {
+    if !state.server_config().public_stats {
+        let claims = match claims_from_headers(&state, &headers).await {
+            Ok(claims) => claims,
+            Err(response) => return response,
+        };
+        if claims.is_none() {
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+    }
+    match state.get_stats().await {
+        crate::apis::stats::GetStatsResponse::Status200_OK(stats) => Json(stats).into_response(),
+        crate::apis::stats::GetStatsResponse::Status401_Unauthorized => {
+            StatusCode::UNAUTHORIZED.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trims_surrounding_whitespace_from_tag() {
+        let params = ListArticlesParams {
+            tag: Some(" rust ".to_string()),
+            ..Default::default()
+        };
+        let trimmed = trim_list_articles_params(params).unwrap();
+        assert_eq!(trimmed.tag, Some("rust".to_string()));
+    }
+
+    #[test]
+    fn require_if_match_rejects_an_unconditional_write_when_required() {
+        let headers = HeaderMap::new();
+        let response = require_if_match(&headers, true).unwrap();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_REQUIRED);
+    }
+
+    #[test]
+    fn require_if_match_passes_a_conditional_write_when_required() {
+        let mut headers = HeaderMap::new();
+        headers.insert(axum::http::header::IF_MATCH, "\"etag\"".parse().unwrap());
+        assert!(require_if_match(&headers, true).is_none());
+    }
+
+    #[test]
+    fn rejects_a_tag_that_is_blank_after_trimming() {
+        let params = ListArticlesParams {
+            tag: Some("   ".to_string()),
+            ..Default::default()
+        };
+        assert!(trim_list_articles_params(params).is_err());
+    }
+
+    fn test_profile(image: Option<&str>) -> crate::models::Profile {
+        crate::models::Profile {
+            username: "jake".to_string(),
+            bio: None,
+            image: image.map(str::to_string),
+            following: crate::models::PersonalizationFlag::NotApplicable,
+        }
+    }
+
+    #[test]
+    fn substitutes_default_avatar_for_an_empty_image() {
+        let mut profile = test_profile(Some(""));
+        apply_default_avatar(&mut profile, &Some("https://example.com/default.png".to_string()));
+        assert_eq!(profile.image, Some("https://example.com/default.png".to_string()));
+    }
+
+    #[test]
+    fn leaves_a_set_image_alone() {
+        let mut profile = test_profile(Some("https://example.com/jake.png"));
+        apply_default_avatar(&mut profile, &Some("https://example.com/default.png".to_string()));
+        assert_eq!(profile.image, Some("https://example.com/jake.png".to_string()));
+    }
+
+    #[test]
+    fn leaves_an_empty_image_alone_when_unconfigured() {
+        let mut profile = test_profile(None);
+        apply_default_avatar(&mut profile, &None);
+        assert_eq!(profile.image, None);
+    }
+
+    #[test]
+    fn render_passes_through_when_under_the_cap() {
+        let response = render(serde_json::json!({"a": "b"}), crate::case::JsonCase::SnakeCase, Some(1024));
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn render_fails_closed_when_over_the_cap() {
+        let response = render(serde_json::json!({"a": "b"}), crate::case::JsonCase::SnakeCase, Some(1));
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn render_is_unbounded_by_default() {
+        let response = render(serde_json::json!({"a": "b"}), crate::case::JsonCase::SnakeCase, None);
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn cookie_value_finds_a_cookie_among_several() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            axum::http::header::COOKIE,
+            HeaderValue::from_static("a=1; token=Token%20abc; b=2"),
+        );
+        assert_eq!(cookie_value(&headers, "token"), Some("Token%20abc"));
+        assert_eq!(cookie_value(&headers, "a"), Some("1"));
+        assert_eq!(cookie_value(&headers, "missing"), None);
+    }
+
+    #[test]
+    fn cookie_value_is_none_without_a_cookie_header() {
+        let headers = HeaderMap::new();
+        assert_eq!(cookie_value(&headers, "token"), None);
+    }
+
+    fn test_claims(user_id: &str) -> Claims {
+        Claims {
+            user_id: user_id.to_string(),
+            username: user_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn prefers_the_header_when_only_the_header_is_present() {
+        let resolved = resolve_conflicting_claims(
+            Some(test_claims("jake")),
+            None,
+            crate::context::ConflictingAuthAction::PreferHeader,
+        )
+        .unwrap();
+        assert_eq!(resolved.unwrap().user_id, "jake");
+    }
+
+    #[test]
+    fn falls_back_to_the_cookie_when_only_the_cookie_is_present() {
+        let resolved = resolve_conflicting_claims(
+            None,
+            Some(test_claims("jake")),
+            crate::context::ConflictingAuthAction::PreferHeader,
+        )
+        .unwrap();
+        assert_eq!(resolved.unwrap().user_id, "jake");
+    }
+
+    #[test]
+    fn agreeing_credentials_resolve_without_conflict() {
+        let resolved = resolve_conflicting_claims(
+            Some(test_claims("jake")),
+            Some(test_claims("jake")),
+            crate::context::ConflictingAuthAction::Reject,
+        )
+        .unwrap();
+        assert_eq!(resolved.unwrap().user_id, "jake");
+    }
+
+    #[test]
+    fn prefer_header_silently_keeps_the_header_on_conflict() {
+        let resolved = resolve_conflicting_claims(
+            Some(test_claims("jake")),
+            Some(test_claims("jacob")),
+            crate::context::ConflictingAuthAction::PreferHeader,
+        )
+        .unwrap();
+        assert_eq!(resolved.unwrap().user_id, "jake");
+    }
+
+    #[test]
+    fn reject_rejects_conflicting_credentials_with_a_400() {
+        let response = resolve_conflicting_claims(
+            Some(test_claims("jake")),
+            Some(test_claims("jacob")),
+            crate::context::ConflictingAuthAction::Reject,
+        )
+        .unwrap_err();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn rejects_account_deletion_without_explicit_confirmation() {
+        assert_eq!(
+            require_delete_confirmation(None).unwrap().status(),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            require_delete_confirmation(Some(false)).unwrap().status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[test]
+    fn allows_account_deletion_with_explicit_confirmation() {
+        assert!(require_delete_confirmation(Some(true)).is_none());
+    }
+
+    #[test]
+    fn check_duplicate_tags_flags_a_tag_posted_twice() {
+        let tags = vec!["rust".to_string(), "rust".to_string(), "axum".to_string()];
+        let errors = check_duplicate_tags(&tags);
+        assert_eq!(errors, vec!["tag 'rust' is duplicated".to_string()]);
+    }
+
+    #[test]
+    fn check_duplicate_tags_is_empty_for_unique_tags() {
+        let tags = vec!["rust".to_string(), "axum".to_string()];
+        assert!(check_duplicate_tags(&tags).is_empty());
+    }
+
+    #[test]
+    fn dedupe_tags_keeps_the_first_occurrence_of_each_tag() {
+        let tags = vec!["rust".to_string(), "axum".to_string(), "rust".to_string()];
+        assert_eq!(dedupe_tags(tags), vec!["rust".to_string(), "axum".to_string()]);
+    }
+
+    #[test]
+    fn serve_with_optional_range_returns_the_whole_body_without_a_range_header() {
+        let response = serve_with_optional_range(b"hello world".to_vec(), None);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(axum::http::header::ACCEPT_RANGES).unwrap(),
+            "bytes"
+        );
+    }
+
+    #[test]
+    fn serve_with_optional_range_returns_a_206_for_a_satisfiable_range() {
+        let response = serve_with_optional_range(b"hello world".to_vec(), Some("bytes=0-4"));
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(
+            response.headers().get(axum::http::header::CONTENT_RANGE).unwrap(),
+            "bytes 0-4/11"
+        );
+    }
+
+    #[test]
+    fn serve_with_optional_range_falls_back_to_the_whole_body_for_an_unsatisfiable_range() {
+        let response = serve_with_optional_range(b"hello world".to_vec(), Some("bytes=100-200"));
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn with_partial_personalization_header_adds_the_header_when_flagged() {
+        let response = with_partial_personalization_header(StatusCode::OK.into_response(), true);
+        assert_eq!(
+            response.headers().get("x-partial-personalization").unwrap(),
+            "true"
+        );
+    }
+
+    #[test]
+    fn with_partial_personalization_header_is_absent_by_default() {
+        let response = with_partial_personalization_header(StatusCode::OK.into_response(), false);
+        assert!(response.headers().get("x-partial-personalization").is_none());
+    }
+
+    #[tokio::test]
+    async fn batch_of_two_article_fetches_returns_both_results() {
+        let inner = Router::new()
+            .route("/a", get(|| async { Json(serde_json::json!({"slug": "a"})) }))
+            .route("/b", get(|| async { Json(serde_json::json!({"slug": "b"})) }));
+        let body = Json(crate::models::BatchRequest {
+            requests: vec![
+                crate::models::BatchSubRequest {
+                    method: "GET".to_string(),
+                    path: "/a".to_string(),
+                },
+                crate::models::BatchSubRequest {
+                    method: "GET".to_string(),
+                    path: "/b".to_string(),
+                },
+            ],
+        });
+        let response = batch(inner, HeaderMap::new(), body).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let results: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            results,
+            serde_json::json!([
+                {"status": StatusCode::OK.as_u16(), "body": {"slug": "a"}},
+                {"status": StatusCode::OK.as_u16(), "body": {"slug": "b"}},
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_forwards_authorization_to_an_auth_required_sub_request() {
+        let inner = Router::new().route(
+            "/api/user",
+            get(|headers: HeaderMap| async move {
+                match headers.get(axum::http::header::AUTHORIZATION) {
+                    Some(value) => Json(serde_json::json!({"authorization": value.to_str().unwrap()}))
+                        .into_response(),
+                    None => StatusCode::UNAUTHORIZED.into_response(),
+                }
+            }),
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert(axum::http::header::AUTHORIZATION, "Token jwt.token.here".parse().unwrap());
+        let body = Json(crate::models::BatchRequest {
+            requests: vec![crate::models::BatchSubRequest {
+                method: "GET".to_string(),
+                path: "/api/user".to_string(),
+            }],
+        });
+        let response = batch(inner, headers, body).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let results: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            results,
+            serde_json::json!([
+                {"status": StatusCode::OK.as_u16(), "body": {"authorization": "Token jwt.token.here"}},
+            ])
+        );
+    }
+}
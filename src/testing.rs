@@ -0,0 +1,21 @@
+//! Helpers for implementors' own integration tests against a router built
+//! with [`crate::build_router`]. Only compiled with the `test-util`
+//! feature, so it never ships in a non-test build.
+
+use axum::body::Body;
+use axum::http::{Method, Request};
+
+/// Builds a `method` request to `uri` with an `Authorization: Token
+/// <token>` header already set, matching the format
+/// [`crate::apis::auth::ClaimsResolver::resolve_claims`]'s default
+/// implementation expects. `body` is sent as-is (already-serialized JSON,
+/// or `Body::empty()` for a bodyless request).
+pub fn authenticated_request(method: Method, uri: &str, token: &str, body: Body) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(axum::http::header::AUTHORIZATION, format!("Token {token}"))
+        .header(axum::http::header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .expect("authenticated_request: request parts are always valid")
+}